@@ -0,0 +1,225 @@
+use std::{
+    panic,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use common::SolveError;
+
+use crate::{
+    answers::{self, Answers, Check},
+    days,
+    input::{self, Provider},
+};
+
+/// An answer cell of the table, with the result of checking it if there was one.
+struct Cell {
+    text: String,
+    check: Option<Check>,
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell { text, check: None }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+/// Runs both parts of every day on its `input.txt` and prints the answers and timings as a table.
+///
+/// A failing day doesn't stop the run, even if it panics, its errors are listed after the table
+/// instead.
+/// With `check` every answer is also compared to the day's recorded answers.
+/// Returns whether every implemented part produced an answer, and no answer failed the check.
+pub fn run(check: bool, provider: &dyn Provider) -> bool {
+    let mut failures = vec![];
+    let mut rows = vec![];
+    let mut parse_total = Duration::ZERO;
+    let mut solve_totals = [Duration::ZERO; 2];
+
+    for day in 1..=25 {
+        let label = format!("{day:02}");
+        let path = input::default_path(day);
+        let input = match input::read(day, &path, provider) {
+            Ok(input) => input,
+            Err(err) => {
+                rows.push((label, vec!["NO INPUT".into()], None));
+                failures.push(format!("Day {day:02}: error: {}: {err}", path.display()));
+                continue;
+            }
+        };
+        let recorded = if check {
+            match Answers::load(&answers::default_path(day)) {
+                Ok(recorded) => recorded,
+                Err(err) => {
+                    rows.push((label, vec!["NO ANSWERS".into()], None));
+                    failures.push(format!("Day {day:02}: error: {err}"));
+                    continue;
+                }
+            }
+        } else {
+            Answers::default()
+        };
+
+        let mut answers = vec![];
+        let mut parse_time = Duration::ZERO;
+        let mut solve_times = vec![];
+
+        for part in 1..=2 {
+            let outcome = match run_caught(day, part, &input) {
+                Ok(outcome) => outcome,
+                Err(panic) => {
+                    failures.push(format!("Day {day:02} part {part}: {panic}"));
+                    answers.push("FAILED".into());
+                    solve_times.push(Duration::ZERO);
+                    continue;
+                }
+            };
+
+            // The parsed input is consumed by the solver, so it's parsed again for every part.
+            parse_time += outcome.parse_time;
+            solve_totals[part as usize - 1] += outcome.solve_time;
+
+            answers.push(match outcome.answer {
+                Ok(answer) if check => {
+                    let result = recorded.check(part, &answer);
+                    if let Check::Fail { expected } = &result {
+                        failures.push(format!(
+                            "Day {day:02} part {part}: expected {expected}, got {answer}"
+                        ));
+                    }
+                    Cell {
+                        text: answer.to_string(),
+                        check: Some(result),
+                    }
+                }
+                Ok(answer) => answer.to_string().into(),
+                Err(days::Error::Solve(SolveError::NotImplemented)) => "not implemented".into(),
+                Err(err) => {
+                    let message = match &err {
+                        days::Error::Parse(err) => {
+                            format!("{}\n  in {}", err.render(&input), path.display())
+                        }
+                        err => format!("error: {err}"),
+                    };
+                    failures.push(format!("Day {day:02} part {part}: {message}"));
+                    "FAILED".into()
+                }
+            });
+            solve_times.push(outcome.solve_time);
+        }
+        parse_total += parse_time;
+
+        rows.push((
+            label,
+            answers,
+            Some([parse_time, solve_times[0], solve_times[1]]),
+        ));
+    }
+
+    // The answers are padded to line up the check results after them, and the columns to fit
+    // the widest cell.
+    let cells = || rows.iter().flat_map(|(_, answers, _)| answers.iter());
+    let text_width = cells().map(|cell| cell.text.len()).max().unwrap_or(0);
+    let cell = |cell: &Cell| match &cell.check {
+        Some(result) => format!("{:<text_width$} {result}", cell.text),
+        None => cell.text.clone(),
+    };
+    let width = cells()
+        .map(|c| cell(c).len())
+        .chain(["Part 1".len(), "Total".len()])
+        .max()
+        .unwrap_or(0);
+
+    let line = |columns: [&str; 6]| {
+        let [day, first, second, parse, part1, part2] = columns;
+        let line = format!(
+            "{day:>3}  {first:<width$}  {second:<width$}  {parse:>10}  {part1:>10}  {part2:>10}"
+        );
+        println!("{}", line.trim_end());
+    };
+
+    line(["Day", "Part 1", "Part 2", "Parse", "Part 1", "Part 2"]);
+    for (label, answers, times) in rows.iter() {
+        let answers = answers.iter().map(cell).collect::<Vec<String>>();
+        let times = times.map_or([""; 3].map(String::from), |times| {
+            times.map(format_duration)
+        });
+        line([
+            label,
+            &answers[0],
+            answers.get(1).map_or("", String::as_str),
+            &times[0],
+            &times[1],
+            &times[2],
+        ]);
+    }
+    line([
+        "",
+        "",
+        "Total",
+        &format_duration(parse_total),
+        &format_duration(solve_totals[0]),
+        &format_duration(solve_totals[1]),
+    ]);
+    line([
+        "",
+        "",
+        "All",
+        &format_duration(parse_total + solve_totals[0] + solve_totals[1]),
+        "",
+        "",
+    ]);
+
+    for failure in failures.iter() {
+        eprintln!("\n{failure}");
+    }
+
+    failures.is_empty()
+}
+
+/// Runs one part like [`days::run`], but catches a panic and returns where and why it happened
+/// instead of letting it end the whole run.
+fn run_caught(day: u8, part: u8, input: &str) -> Result<days::Outcome, String> {
+    let message = Arc::new(Mutex::new(String::new()));
+    let previous = panic::take_hook();
+    let hook_message = Arc::clone(&message);
+    panic::set_hook(Box::new(move |info| {
+        *hook_message.lock().unwrap() = info.to_string();
+    }));
+
+    let outcome = panic::catch_unwind(|| days::run(day, part, input));
+
+    panic::set_hook(previous);
+    outcome.map_err(|_| message.lock().unwrap().clone())
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs < 1e-3 {
+        format!("{:.1}µs", secs * 1e6)
+    } else if secs < 1.0 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{secs:.2}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_caught;
+
+    #[test]
+    fn catches_panics() {
+        assert!(run_caught(1, 1, "1abc2").unwrap().answer.is_ok());
+
+        let panic = run_caught(1, 3, "1abc2").err().unwrap();
+        assert!(panic.starts_with("panicked at "));
+        assert!(panic.ends_with("Invalid part 3"));
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use common::Answer;
+use serde::Deserialize;
+
+/// The known correct answers for a day's `input.txt`, as recorded in its `answers.toml`.
+///
+/// Integers are written as TOML integers, e.g. `part1 = 53651`, text as strings and grids as
+/// arrays of their rows. Integers too large for TOML are written as `{ big_integer = "..." }`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    part1: Option<Recorded>,
+    part2: Option<Recorded>,
+}
+
+/// An answer as written in `answers.toml`, which is read into the [`Answer`] it stands for.
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "RecordedValue")]
+struct Recorded(Answer);
+
+#[derive(Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum RecordedValue {
+    Integer(i64),
+    Text(String),
+    Grid(Vec<String>),
+    BigInteger { big_integer: String },
+}
+
+impl TryFrom<RecordedValue> for Recorded {
+    type Error = String;
+
+    fn try_from(value: RecordedValue) -> Result<Self, Self::Error> {
+        Ok(Recorded(match value {
+            RecordedValue::Integer(v) => v.into(),
+            RecordedValue::Text(text) => Answer::Text(text),
+            RecordedValue::Grid(rows) => Answer::Grid(rows),
+            RecordedValue::BigInteger { big_integer } => big_integer
+                .parse::<i128>()
+                .map_err(|e| format!("invalid big_integer {big_integer:?}: {e}"))?
+                .into(),
+        }))
+    }
+}
+
+/// How an answer compares to the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail {
+        expected: Answer,
+    },
+    /// There is no recorded answer to compare with.
+    Unknown,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Pass => write!(f, "PASS"),
+            Check::Fail { .. } => write!(f, "FAIL"),
+            Check::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// The location of a day's recorded answers, relative to the workspace root.
+pub fn default_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("day{day:02}")).join("answers.toml")
+}
+
+impl Answers {
+    /// Reads the answers at `path`, where a missing file means nothing is recorded.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read answers file {}: {e}", path.display()))?;
+        toml::from_str(&text).map_err(|e| format!("invalid answers file {}: {e}", path.display()))
+    }
+
+    pub fn check(&self, part: u8, answer: &Answer) -> Check {
+        let expected = match part {
+            1 => &self.part1,
+            2 => &self.part2,
+            p => panic!("Invalid part {p}"),
+        };
+
+        match expected {
+            Some(Recorded(expected)) if expected == answer => Check::Pass,
+            Some(Recorded(expected)) => Check::Fail {
+                expected: expected.clone(),
+            },
+            None => Check::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use common::Answer;
+
+    use super::{Answers, Check};
+
+    fn answers(text: &str) -> Answers {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn compares_typed_answers() {
+        let recorded = answers("part1 = 5\npart2 = \"5\"");
+
+        assert_eq!(recorded.check(1, &Answer::Integer(5)), Check::Pass);
+        assert_eq!(
+            recorded.check(1, &Answer::Text("5".into())),
+            Check::Fail {
+                expected: Answer::Integer(5)
+            }
+        );
+        assert_eq!(recorded.check(2, &Answer::Text("5".into())), Check::Pass);
+        assert_eq!(
+            recorded.check(2, &Answer::Integer(5)),
+            Check::Fail {
+                expected: Answer::Text("5".into())
+            }
+        );
+    }
+
+    #[test]
+    fn reads_big_integers_and_grids() {
+        let recorded =
+            answers("part1 = { big_integer = \"170141183460469231731687303715884105727\" }\npart2 = [\"#.\", \".#\"]");
+
+        assert_eq!(recorded.check(1, &Answer::from(i128::MAX)), Check::Pass);
+        assert_eq!(
+            recorded.check(2, &Answer::Grid(vec!["#.".into(), ".#".into()])),
+            Check::Pass
+        );
+        assert_eq!(answers("").check(1, &Answer::Integer(5)), Check::Unknown);
+    }
+
+    #[test]
+    fn load_reports_bad_files() {
+        let path = env::temp_dir().join(format!("aoc-answers-{}.toml", process::id()));
+        fs::write(&path, "part1 = ").unwrap();
+        let loaded = Answers::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.unwrap_err().starts_with("invalid answers file"));
+        assert!(Answers::load(&path).is_ok());
+    }
+}
@@ -0,0 +1,242 @@
+use std::{collections::BTreeMap, fs, io, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    all::format_duration,
+    days,
+    input::{self, Provider},
+};
+
+/// How a benchmark run is done and judged.
+pub struct Options {
+    /// Timed runs of every part.
+    pub iterations: usize,
+    /// Untimed runs of every part before the timed ones.
+    pub warmup: usize,
+    /// How much slower than the baseline, in percent, counts as a regression.
+    pub threshold: f64,
+}
+
+/// Summary statistics of a number of timed runs, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub median: u64,
+    pub p95: u64,
+    pub stddev: u64,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut nanos = samples
+            .iter()
+            .map(|sample| sample.as_nanos() as u64)
+            .collect::<Vec<u64>>();
+        nanos.sort();
+
+        let n = nanos.len();
+        // Nearest-rank percentiles, where the median of an even count is the upper middle.
+        let percentile = |p: usize| nanos[(n * p).div_ceil(100).max(1) - 1];
+        let median = nanos[n / 2];
+
+        let mean = nanos.iter().sum::<u64>() as f64 / n as f64;
+        let variance = nanos
+            .iter()
+            .map(|&x| (x as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Self {
+            median,
+            p95: percentile(95),
+            stddev: variance.sqrt() as u64,
+        }
+    }
+
+    /// How much slower this is than `baseline`, in percent, going by the medians.
+    pub fn change(&self, baseline: &Stats) -> f64 {
+        (self.median as f64 / baseline.median.max(1) as f64 - 1.0) * 100.0
+    }
+}
+
+/// The timings of one day, with `None` for parts that don't produce an answer.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DayStats {
+    pub parse: Option<Stats>,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+/// The timings of every benchmarked day, as saved to a baseline file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub days: BTreeMap<u8, DayStats>,
+}
+
+impl Baseline {
+    /// Loads a baseline, or an empty one if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|err| err.to_string()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Failed to serialize the baseline");
+        fs::write(path, json + "\n")
+    }
+}
+
+/// Times parsing and both parts of a day on `input`, or `None` for parts that fail.
+///
+/// Parsing is timed on every run of either part, so it gets twice as many samples.
+fn bench_day(day: u8, input: &str, options: &Options) -> DayStats {
+    let mut parse_times = vec![];
+    let mut solve_stats = vec![];
+
+    for part in 1..=2 {
+        for _ in 0..options.warmup {
+            days::run(day, part, input);
+        }
+
+        let mut solve_times = vec![];
+        for _ in 0..options.iterations {
+            let outcome = days::run(day, part, input);
+            if outcome.answer.is_err() {
+                solve_times.clear();
+                break;
+            }
+            parse_times.push(outcome.parse_time);
+            solve_times.push(outcome.solve_time);
+        }
+        solve_stats.push((!solve_times.is_empty()).then(|| Stats::new(&solve_times)));
+    }
+
+    DayStats {
+        parse: (!parse_times.is_empty()).then(|| Stats::new(&parse_times)),
+        part1: solve_stats[0],
+        part2: solve_stats[1],
+    }
+}
+
+/// Benchmarks the given days on their `input.txt` and prints the statistics of every stage,
+/// comparing them to `baseline` when it has them.
+///
+/// Returns the new timings and whether none of them regressed beyond the threshold.
+pub fn run(
+    days: &[u8],
+    options: &Options,
+    baseline: &Baseline,
+    provider: &dyn Provider,
+) -> (Baseline, bool) {
+    println!(
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "Day", "Stage", "Median", "p95", "Std dev", "Baseline", "Change"
+    );
+
+    let mut results = Baseline::default();
+    let mut regressions = vec![];
+
+    for &day in days {
+        let path = input::default_path(day);
+        let input = match input::read(day, &path, provider) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("{day:>3}  NO INPUT");
+                eprintln!("Day {day:02}: error: {}: {err}", path.display());
+                continue;
+            }
+        };
+
+        let stats = bench_day(day, &input, options);
+        let previous = baseline.days.get(&day).cloned().unwrap_or_default();
+
+        for (stage, stats, previous) in [
+            ("parse", stats.parse, previous.parse),
+            ("part 1", stats.part1, previous.part1),
+            ("part 2", stats.part2, previous.part2),
+        ] {
+            let Some(stats) = stats else {
+                println!("{:>3}  {stage:<6}  {:>10}", format!("{day:02}"), "-");
+                continue;
+            };
+
+            let (base, change) = match previous {
+                Some(previous) => {
+                    let change = stats.change(&previous);
+                    if change > options.threshold {
+                        regressions.push(format!(
+                            "Day {day:02} {stage}: {} -> {} ({change:+.1}%)",
+                            nanos(previous.median),
+                            nanos(stats.median)
+                        ));
+                    }
+                    (nanos(previous.median), format!("{change:+.1}%"))
+                }
+                None => (String::new(), String::new()),
+            };
+
+            println!(
+                "{:>3}  {stage:<6}  {:>10}  {:>10}  {:>10}  {base:>10}  {change:>8}",
+                format!("{day:02}"),
+                nanos(stats.median),
+                nanos(stats.p95),
+                nanos(stats.stddev),
+            );
+        }
+
+        results.days.insert(day, stats);
+    }
+
+    if !regressions.is_empty() {
+        eprintln!(
+            "\nRegressions of more than {}% against the baseline:",
+            options.threshold
+        );
+        for regression in regressions.iter() {
+            eprintln!("  {regression}");
+        }
+    }
+
+    (results, regressions.is_empty())
+}
+
+fn nanos(nanos: u64) -> String {
+    format_duration(Duration::from_nanos(nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn stats_of_samples() {
+        let samples = (1..=20).map(Duration::from_nanos).collect::<Vec<_>>();
+        let stats = Stats::new(&samples);
+
+        assert_eq!(stats.median, 11);
+        assert_eq!(stats.p95, 19);
+        // The population standard deviation of 1 to 20 is sqrt(33.25).
+        assert_eq!(stats.stddev, 5);
+    }
+
+    #[test]
+    fn change_against_baseline() {
+        let baseline = Stats {
+            median: 100,
+            p95: 120,
+            stddev: 10,
+        };
+        let slower = Stats {
+            median: 125,
+            ..baseline
+        };
+
+        assert_eq!(slower.change(&baseline), 25.0);
+        assert!((baseline.change(&slower) + 20.0).abs() < 1e-9);
+    }
+}
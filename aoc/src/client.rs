@@ -0,0 +1,101 @@
+use std::fmt::Display;
+
+use reqwest::{blocking, header::COOKIE};
+
+use crate::input::{self, Provider};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (Advent of Code 2023 solutions runner)"
+);
+
+/// Why a request to the site failed.
+#[derive(Debug)]
+pub enum Error {
+    /// Requests need the session cookie of a logged in user.
+    MissingSession,
+    Http(reqwest::Error),
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Http(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(f, "no session cookie, set AOC_SESSION to one"),
+            Error::Http(err) => err.fmt(f),
+        }
+    }
+}
+
+/// A client for the Advent of Code site, or anything serving the same routes at `base_url`.
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    http: blocking::Client,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        let http = blocking::Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("Failed to set up the HTTP client");
+
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session,
+            http,
+        }
+    }
+
+    fn session_cookie(&self) -> Result<String, Error> {
+        let session = self.session.as_ref().ok_or(Error::MissingSession)?;
+        Ok(format!("session={session}"))
+    }
+
+    pub fn input(&self, year: u32, day: u8) -> Result<String, Error> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+
+        let input = self
+            .http
+            .get(url)
+            .header(COOKIE, self.session_cookie()?)
+            .send()?
+            .error_for_status()?
+            .text()?;
+        Ok(input)
+    }
+
+    /// Posts an answer, returning the page the site responds with.
+    pub fn submit(&self, year: u32, day: u8, part: u8, answer: &str) -> Result<String, Error> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+
+        let page = self
+            .http
+            .post(url)
+            .header(COOKIE, self.session_cookie()?)
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)])
+            .send()?
+            .error_for_status()?
+            .text()?;
+        Ok(page)
+    }
+}
+
+impl Provider for Client {
+    fn input(&self, year: u32, day: u8) -> Result<String, input::Error> {
+        eprintln!(
+            "Downloading input for {year} day {day} from {}",
+            self.base_url
+        );
+        Ok(Client::input(self, year, day)?)
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use common::{Answer, ParseError, Solution, SolveError};
+
+/// Why running a day failed.
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl From<SolveError> for Error {
+    fn from(err: SolveError) -> Self {
+        Error::Solve(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(err) => err.fmt(f),
+            Error::Solve(err) => err.fmt(f),
+        }
+    }
+}
+
+/// The result of running one part, along with how long parsing and solving took.
+pub struct Outcome {
+    pub answer: Result<Answer, Error>,
+    pub parse_time: Duration,
+    /// Zero if parsing failed.
+    pub solve_time: Duration,
+}
+
+/// Calls the generic function `f` with the given day's [`Solution`] and the arguments.
+macro_rules! with_day {
+    ($day:expr, $f:ident($($arg:expr),*)) => {
+        match $day {
+            1 => $f::<day01::Day01>($($arg),*),
+            2 => $f::<day02::Day02>($($arg),*),
+            3 => $f::<day03::Day03>($($arg),*),
+            4 => $f::<day04::Day04>($($arg),*),
+            5 => $f::<day05::Day05>($($arg),*),
+            6 => $f::<day06::Day06>($($arg),*),
+            7 => $f::<day07::Day07>($($arg),*),
+            8 => $f::<day08::Day08>($($arg),*),
+            9 => $f::<day09::Day09>($($arg),*),
+            10 => $f::<day10::Day10>($($arg),*),
+            11 => $f::<day11::Day11>($($arg),*),
+            12 => $f::<day12::Day12>($($arg),*),
+            13 => $f::<day13::Day13>($($arg),*),
+            14 => $f::<day14::Day14>($($arg),*),
+            15 => $f::<day15::Day15>($($arg),*),
+            16 => $f::<day16::Day16>($($arg),*),
+            17 => $f::<day17::Day17>($($arg),*),
+            18 => $f::<day18::Day18>($($arg),*),
+            19 => $f::<day19::Day19>($($arg),*),
+            20 => $f::<day20::Day20>($($arg),*),
+            21 => $f::<day21::Day21>($($arg),*),
+            22 => $f::<day22::Day22>($($arg),*),
+            23 => $f::<day23::Day23>($($arg),*),
+            24 => $f::<day24::Day24>($($arg),*),
+            25 => $f::<day25::Day25>($($arg),*),
+            d => panic!("Invalid day {d}"),
+        }
+    };
+}
+
+/// Parses `input` with the given day's parser and runs the requested part on it.
+pub fn run(day: u8, part: u8, input: &str) -> Outcome {
+    with_day!(day, solve(part, input))
+}
+
+/// Parses `input` with the given day's parser and writes it as a Graphviz graph, or returns
+/// `None` if the day's input isn't a graph.
+pub fn dot(day: u8, input: &str) -> Result<Option<String>, Error> {
+    with_day!(day, graph(input))
+}
+
+/// Parses `input` with the given day's parser and draws it as the requested part sees it, or
+/// returns `None` if the day has nothing to draw.
+pub fn svg(day: u8, part: u8, input: &str) -> Result<Option<String>, Error> {
+    with_day!(day, draw(part, input))
+}
+
+fn solve<S: Solution>(part: u8, input: &str) -> Outcome {
+    let start = Instant::now();
+    let parsed = S::parse(input);
+    let parse_time = start.elapsed();
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return Outcome {
+                answer: Err(err.into()),
+                parse_time,
+                solve_time: Duration::ZERO,
+            }
+        }
+    };
+
+    let start = Instant::now();
+    let answer = match part {
+        1 => S::solve_part_one(parsed),
+        2 => S::solve_part_two(parsed),
+        p => panic!("Invalid part {p}"),
+    };
+    let solve_time = start.elapsed();
+
+    Outcome {
+        answer: answer.map_err(Error::from),
+        parse_time,
+        solve_time,
+    }
+}
+
+fn draw<S: Solution>(part: u8, input: &str) -> Result<Option<String>, Error> {
+    let parsed = S::parse(input)?;
+    Ok(S::svg(parsed, part).transpose()?)
+}
+
+fn graph<S: Solution>(input: &str) -> Result<Option<String>, Error> {
+    Ok(S::dot(S::parse(input)?))
+}
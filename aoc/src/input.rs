@@ -0,0 +1,271 @@
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "download_input")]
+use crate::client::{self, Client};
+
+/// Why an input couldn't be provided.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The input isn't cached, and downloading is disabled.
+    NotCached {
+        year: u32,
+        day: u8,
+    },
+    #[cfg(feature = "download_input")]
+    Download(client::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+#[cfg(feature = "download_input")]
+impl From<client::Error> for Error {
+    fn from(err: client::Error) -> Self {
+        Error::Download(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(err) => err.fmt(f),
+            Error::NotCached { year, day } => {
+                write!(f, "the input for {year} day {day} isn't cached")
+            }
+            #[cfg(feature = "download_input")]
+            Error::Download(err) => write!(f, "failed to download the input: {err}"),
+        }
+    }
+}
+
+/// A source of puzzle inputs.
+pub trait Provider {
+    fn input(&self, year: u32, day: u8) -> Result<String, Error>;
+}
+
+/// Inputs saved on disk as `<dir>/<year>/dayNN.txt`.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, year: u32, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{day:02}.txt"))
+    }
+
+    #[cfg(feature = "download_input")]
+    pub fn store(&self, year: u32, day: u8, input: &str) -> io::Result<()> {
+        let path = self.path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, input)
+    }
+}
+
+impl Provider for Cache {
+    fn input(&self, year: u32, day: u8) -> Result<String, Error> {
+        match fs::read_to_string(self.path(year, day)) {
+            Ok(input) => Ok(input),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Err(Error::NotCached { year, day })
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Serves inputs from a cache, fetching the ones it's missing from `source` and storing them.
+#[cfg(feature = "download_input")]
+pub struct Cached<P> {
+    cache: Cache,
+    source: P,
+}
+
+#[cfg(feature = "download_input")]
+impl<P> Cached<P> {
+    pub fn new(cache: Cache, source: P) -> Self {
+        Self { cache, source }
+    }
+}
+
+#[cfg(feature = "download_input")]
+impl<P: Provider> Provider for Cached<P> {
+    fn input(&self, year: u32, day: u8) -> Result<String, Error> {
+        match self.cache.input(year, day) {
+            Err(Error::NotCached { .. }) => {
+                let input = self.source.input(year, day)?;
+                self.cache.store(year, day, &input)?;
+                Ok(input)
+            }
+            cached => cached,
+        }
+    }
+}
+
+/// Where to get inputs that aren't in the workspace from.
+#[derive(clap::Args)]
+pub struct Options {
+    /// Directory to cache inputs in, defaults to `aoc` in the user's cache directory.
+    #[arg(long, global = true, env = "AOC_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+    /// Only use cached inputs instead of downloading missing ones.
+    #[cfg(feature = "download_input")]
+    #[arg(long, global = true)]
+    offline: bool,
+    /// The site to download inputs from, e.g. a local server standing in for it.
+    #[cfg(feature = "download_input")]
+    #[arg(long, global = true, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// The session cookie from adventofcode.com, needed to download inputs.
+    #[cfg(feature = "download_input")]
+    #[arg(long, global = true, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+}
+
+impl Options {
+    pub fn cache_dir(&self) -> PathBuf {
+        self.cache_dir.clone().unwrap_or_else(default_cache_dir)
+    }
+
+    #[cfg(feature = "download_input")]
+    pub fn client(&self) -> Client {
+        Client::new(&self.base_url, self.session.clone())
+    }
+
+    pub fn provider(&self) -> Box<dyn Provider> {
+        let cache = Cache::new(self.cache_dir());
+
+        #[cfg(feature = "download_input")]
+        if !self.offline {
+            return Box::new(Cached::new(cache, self.client()));
+        }
+
+        Box::new(cache)
+    }
+}
+
+fn default_cache_dir() -> PathBuf {
+    let cache = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(env::temp_dir);
+    cache.join("aoc")
+}
+
+/// The location of a day's puzzle input, relative to the workspace root.
+pub fn default_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("day{day:02}")).join("input.txt")
+}
+
+/// Reads the input file at `path`. If that's the day's [`default_path`] and it doesn't exist
+/// yet, it's fetched from `provider` and saved there first; any other path has to exist.
+pub fn read(day: u8, path: &Path, provider: &dyn Provider) -> Result<String, Error> {
+    if path == default_path(day) && !path.exists() {
+        let input = provider.input(crate::YEAR, day)?;
+        fs::write(path, &input)?;
+        return Ok(input);
+    }
+
+    Ok(fs::read_to_string(path)?)
+}
+
+#[cfg(all(test, feature = "download_input"))]
+mod tests {
+    use std::{env, fs, io, path::PathBuf, process};
+
+    use super::{Cache, Cached, Error, Provider};
+    use crate::{client::Client, stub_server::StubServer};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-input-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn cache_hit_never_downloads() {
+        let server = StubServer::start(|_| (500, String::new()));
+        let cache = Cache::new(temp_dir("hit"));
+        cache.store(2023, 1, "cached input").unwrap();
+
+        let provider = Cached::new(cache, Client::new(server.url(), Some("abc".into())));
+
+        assert_eq!(provider.input(2023, 1).unwrap(), "cached input");
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn cache_miss_downloads_once() {
+        let server = StubServer::start(|_| (200, "downloaded input".into()));
+        let dir = temp_dir("miss");
+        let provider = Cached::new(
+            Cache::new(&dir),
+            Client::new(server.url(), Some("abc".into())),
+        );
+
+        assert_eq!(provider.input(2023, 5).unwrap(), "downloaded input");
+        assert_eq!(provider.input(2023, 5).unwrap(), "downloaded input");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2023/day/5/input");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc"));
+        assert!(requests[0]
+            .header("user-agent")
+            .unwrap()
+            .starts_with("aoc/"));
+        assert_eq!(
+            fs::read_to_string(dir.join("2023").join("day05.txt")).unwrap(),
+            "downloaded input"
+        );
+    }
+
+    #[test]
+    fn failed_download_is_not_cached() {
+        let server = StubServer::start(|_| (404, "Not found".into()));
+        let dir = temp_dir("failed");
+        let provider = Cached::new(
+            Cache::new(&dir),
+            Client::new(server.url(), Some("abc".into())),
+        );
+
+        assert!(provider.input(2023, 26).is_err());
+        assert!(!dir.join("2023").join("day26.txt").exists());
+    }
+
+    #[test]
+    fn missing_custom_path_is_an_error() {
+        struct Unreachable;
+
+        impl Provider for Unreachable {
+            fn input(&self, _: u32, _: u8) -> Result<String, Error> {
+                panic!("only the default path should be fetched")
+            }
+        }
+
+        let path = temp_dir("custom").join("test.txt");
+
+        assert!(matches!(
+            super::read(17, &path, &Unreachable),
+            Err(Error::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+        assert!(!path.exists());
+    }
+}
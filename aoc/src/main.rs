@@ -0,0 +1,271 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use answers::{Answers, Check};
+use clap::{Parser, Subcommand};
+
+mod all;
+mod answers;
+#[cfg(feature = "bench")]
+mod bench;
+#[cfg(feature = "download_input")]
+mod client;
+mod days;
+mod input;
+#[cfg(all(test, feature = "download_input"))]
+mod stub_server;
+#[cfg(feature = "download_input")]
+mod submit;
+
+pub const YEAR: u32 = 2023;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    input: input::Options,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one part of a single day.
+    Run {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Input file to use instead of the day's `input.txt`, e.g. `day17/test_input.txt`.
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Compare the answer to the one recorded in the day's `answers.toml`.
+        #[arg(long)]
+        check: bool,
+        /// Also draw the input as an SVG file next to it, e.g. `day18/input.part2.svg`, for
+        /// the days that can.
+        #[arg(long)]
+        svg: bool,
+        /// Also write the input as a Graphviz graph next to it, e.g. `day20/input.dot`, for
+        /// the days whose input is one.
+        #[arg(long)]
+        dot: bool,
+    },
+    /// Run both parts of every day and print a table of the answers and timings.
+    All {
+        /// Compare every answer to the ones recorded in each day's `answers.toml`.
+        #[arg(long)]
+        check: bool,
+    },
+    /// Time parsing and both parts of every day, or just one, and compare them to a baseline.
+    #[cfg(feature = "bench")]
+    Bench {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Timed runs of every part.
+        #[arg(short = 'n', long, default_value_t = 20)]
+        iterations: usize,
+        /// Untimed runs of every part before the timed ones.
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+        /// File with earlier timings to compare to.
+        #[arg(long, default_value = "bench.json")]
+        baseline: PathBuf,
+        /// Save the timings to the baseline file, keeping the days that weren't run.
+        #[arg(long)]
+        save: bool,
+        /// How much slower than the baseline a median may get, in percent.
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Solve one part of a day on its `input.txt` and submit the answer.
+    ///
+    /// Answers already known to be wrong are never submitted again, and neither is anything
+    /// while the site asks to wait.
+    #[cfg(feature = "download_input")]
+    Submit {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let provider = cli.input.provider();
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            check,
+            svg,
+            dot,
+        } => {
+            // The recorded answers only apply to the day's own input.
+            let answers = match (check, &input) {
+                (true, None) => Answers::load(&answers::default_path(day)).unwrap_or_else(|err| {
+                    eprintln!("error: {err}");
+                    process::exit(1);
+                }),
+                _ => Answers::default(),
+            };
+            let path = input.unwrap_or_else(|| input::default_path(day));
+            let input = input::read(day, &path, provider.as_ref()).unwrap_or_else(|err| {
+                eprintln!("error: {}: {err}", path.display());
+                process::exit(1);
+            });
+
+            match days::run(day, part, &input).answer {
+                Ok(answer) if check => {
+                    let result = answers.check(part, &answer);
+                    println!("Day {day:02} part {part}: {answer} {result}");
+                    if let Check::Fail { expected } = result {
+                        eprintln!("expected {expected}");
+                        process::exit(1);
+                    }
+                }
+                Ok(answer) => println!("Day {day:02} part {part}: {answer}"),
+                Err(days::Error::Parse(err)) => {
+                    eprintln!("{}", err.render(&input));
+                    eprintln!("  in {}", path.display());
+                    process::exit(1);
+                }
+                Err(err) => {
+                    eprintln!("error: day {day:02} part {part}: {err}");
+                    process::exit(1);
+                }
+            }
+
+            if svg {
+                let svg_path = path.with_extension(format!("part{part}.svg"));
+                write_drawing(day, part, &svg_path, days::svg(day, part, &input));
+            }
+            if dot {
+                write_drawing(
+                    day,
+                    part,
+                    &path.with_extension("dot"),
+                    days::dot(day, &input),
+                );
+            }
+        }
+        Command::All { check } => {
+            if !all::run(check, provider.as_ref()) {
+                process::exit(1);
+            }
+        }
+        #[cfg(feature = "bench")]
+        Command::Bench {
+            day,
+            iterations,
+            warmup,
+            baseline: path,
+            save,
+            threshold,
+        } => {
+            let mut baseline = bench::Baseline::load(&path).unwrap_or_else(|err| {
+                eprintln!("error: {}: {err}", path.display());
+                process::exit(1);
+            });
+            let days = match day {
+                Some(day) => vec![day],
+                None => (1..=25).collect(),
+            };
+            let options = bench::Options {
+                iterations: iterations.max(1),
+                warmup,
+                threshold,
+            };
+
+            let (results, passed) = bench::run(&days, &options, &baseline, provider.as_ref());
+
+            if save {
+                baseline.days.extend(results.days);
+                if let Err(err) = baseline.save(&path) {
+                    eprintln!("error: {}: {err}", path.display());
+                    process::exit(1);
+                }
+            }
+            if !passed {
+                process::exit(1);
+            }
+        }
+        #[cfg(feature = "download_input")]
+        Command::Submit { day, part } => {
+            let path = input::default_path(day);
+            let input = input::read(day, &path, provider.as_ref()).unwrap_or_else(|err| {
+                eprintln!("error: {}: {err}", path.display());
+                process::exit(1);
+            });
+
+            let answer = match days::run(day, part, &input).answer {
+                Ok(answer) => answer,
+                Err(days::Error::Parse(err)) => {
+                    eprintln!("{}", err.render(&input));
+                    eprintln!("  in {}", path.display());
+                    process::exit(1);
+                }
+                Err(err) => {
+                    eprintln!("error: day {day:02} part {part}: {err}");
+                    process::exit(1);
+                }
+            };
+            println!("Day {day:02} part {part}: submitting {answer}");
+
+            let client = cli.input.client();
+            let submitter = submit::Submitter::new(&client, &cli.input.cache_dir(), YEAR);
+            match submitter.submit(YEAR, day, part, &answer.to_string()) {
+                Ok(submit::Outcome::Submitted(response)) => {
+                    println!("{}", response.verdict);
+                    if let Some(wait) = response.wait {
+                        println!("wait {}s before submitting again", wait.as_secs());
+                    }
+                    if response.verdict != submit::Verdict::Correct {
+                        process::exit(1);
+                    }
+                }
+                Ok(submit::Outcome::KnownWrong(verdict)) => {
+                    eprintln!("error: {answer} was already submitted, it's {verdict}");
+                    process::exit(1);
+                }
+                Ok(submit::Outcome::MustWait(wait)) => {
+                    eprintln!("error: wait {}s before submitting again", wait.as_secs());
+                    process::exit(1);
+                }
+                Err(err) => {
+                    eprintln!("error: failed to submit: {err}");
+                    process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// Writes a drawing of the day's input to `path`, exiting if there's none or it failed.
+fn write_drawing(day: u8, part: u8, path: &Path, drawing: Result<Option<String>, days::Error>) {
+    let drawing = match drawing {
+        Ok(Some(drawing)) => drawing,
+        Ok(None) => {
+            eprintln!(
+                "error: day {day:02} has nothing to draw as {}",
+                path.display()
+            );
+            process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("error: day {day:02} part {part}: {err}");
+            process::exit(1);
+        }
+    };
+    if let Err(err) = fs::write(path, drawing) {
+        eprintln!("error: {}: {err}", path.display());
+        process::exit(1);
+    }
+    println!("Drew {}", path.display());
+}
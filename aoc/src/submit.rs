@@ -0,0 +1,366 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::{self, Client};
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// The previous answer was submitted too recently, so this one wasn't checked.
+    TooSoon,
+    /// The part is already solved, or its first part isn't.
+    WrongLevel,
+    /// A response that isn't recognized, holding its text.
+    Unrecognized(String),
+}
+
+impl Verdict {
+    fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooSoon => write!(f, "submitted too soon"),
+            Verdict::WrongLevel => write!(f, "not the right level, is it already solved?"),
+            Verdict::Unrecognized(text) => write!(f, "unrecognized response: {text}"),
+        }
+    }
+}
+
+/// The site's response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// How long to wait before submitting another answer.
+    pub wait: Option<Duration>,
+}
+
+/// Reads the verdict and wait time from the page returned for a submitted answer.
+pub fn parse_response(page: &str) -> Response {
+    let text = article_text(page);
+
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unrecognized(text.clone())
+    };
+
+    Response {
+        verdict,
+        wait: parse_wait(&text),
+    }
+}
+
+/// The text of the page's `<article>`, which holds the response, without any markup.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Finds either "You have 1m 5s left to wait" or "please wait 5 minutes before trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+        let mut secs = 0;
+        for amount in left.split_whitespace() {
+            let (n, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let n: u64 = n.parse().ok()?;
+            secs += match unit {
+                "h" => n * 3600,
+                "m" => n * 60,
+                "s" => n,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(secs));
+    }
+
+    let text = text.to_lowercase();
+    let (_, rest) = text.split_once("please wait ")?;
+    let (amount, _) = rest.split_once(" minute")?;
+    let minutes = match amount {
+        "one" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+/// The local record of submissions for a year, kept to avoid pointless requests.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Submissions {
+    /// Seconds since the Unix epoch until which the site won't accept answers.
+    wait_until: Option<u64>,
+    #[serde(default)]
+    wrong: Vec<WrongGuess>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WrongGuess {
+    day: u8,
+    part: u8,
+    answer: String,
+    verdict: String,
+}
+
+/// Why an answer couldn't be submitted.
+#[derive(Debug)]
+pub enum Error {
+    Client(client::Error),
+    Io(io::Error),
+    Record(String),
+}
+
+impl From<client::Error> for Error {
+    fn from(err: client::Error) -> Self {
+        Error::Client(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Client(err) => err.fmt(f),
+            Error::Io(err) => err.fmt(f),
+            Error::Record(err) => write!(f, "invalid submissions record: {err}"),
+        }
+    }
+}
+
+/// What happened to an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Submitted(Response),
+    /// The answer was already submitted and found to be wrong, with the verdict it got then.
+    KnownWrong(String),
+    /// The site asked to wait this much longer before submitting again.
+    MustWait(Duration),
+}
+
+/// Submits answers through a client, keeping a record of wrong guesses and wait times in `dir`.
+pub struct Submitter<'a> {
+    client: &'a Client,
+    record: PathBuf,
+}
+
+impl<'a> Submitter<'a> {
+    pub fn new(client: &'a Client, dir: &Path, year: u32) -> Self {
+        Self {
+            client,
+            record: dir.join(year.to_string()).join("submissions.toml"),
+        }
+    }
+
+    fn load(&self) -> Result<Submissions, Error> {
+        match fs::read_to_string(&self.record) {
+            Ok(text) => toml::from_str(&text).map_err(|e| Error::Record(e.to_string())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn save(&self, submissions: &Submissions) -> Result<(), Error> {
+        if let Some(dir) = self.record.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(submissions).map_err(|e| Error::Record(e.to_string()))?;
+        Ok(fs::write(&self.record, text)?)
+    }
+
+    /// Submits `answer`, unless it's known to be wrong or the site asked to wait.
+    pub fn submit(&self, year: u32, day: u8, part: u8, answer: &str) -> Result<Outcome, Error> {
+        let mut submissions = self.load()?;
+
+        if let Some(guess) = submissions
+            .wrong
+            .iter()
+            .find(|g| g.day == day && g.part == part && g.answer == answer)
+        {
+            return Ok(Outcome::KnownWrong(guess.verdict.clone()));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("The clock is before 1970")
+            .as_secs();
+        if let Some(until) = submissions.wait_until.filter(|until| *until > now) {
+            return Ok(Outcome::MustWait(Duration::from_secs(until - now)));
+        }
+
+        let response = parse_response(&self.client.submit(year, day, part, answer)?);
+
+        if response.verdict.is_wrong() {
+            submissions.wrong.push(WrongGuess {
+                day,
+                part,
+                answer: answer.to_string(),
+                verdict: response.verdict.to_string(),
+            });
+        }
+        submissions.wait_until = response.wait.map(|wait| now + wait.as_secs());
+        self.save(&submissions)?;
+
+        Ok(Outcome::Submitted(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process, time::Duration};
+
+    use super::{parse_response, Outcome, Response, Submitter, Verdict};
+    use crate::{client::Client, stub_server::StubServer};
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main><article><p>{article}</p></article></main></body></html>")
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-submit-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer! You are <span class=\"day-success\">one gold star</span> closer."
+            )),
+            Response {
+                verdict: Verdict::Correct,
+                wait: None
+            }
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high. Please wait one minute before trying again."
+            )),
+            Response {
+                verdict: Verdict::TooHigh,
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."
+            )),
+            Response {
+                verdict: Verdict::TooLow,
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 12s left to wait."
+            )),
+            Response {
+                verdict: Verdict::TooSoon,
+                wait: Some(Duration::from_secs(72))
+            }
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ))
+            .verdict,
+            Verdict::WrongLevel
+        );
+    }
+
+    #[test]
+    fn never_resubmits_a_wrong_answer() {
+        let server = StubServer::start(|_| {
+            (
+                200,
+                page("That's not the right answer; your answer is too low."),
+            )
+        });
+        let client = Client::new(server.url(), Some("abc".into()));
+        let submitter = Submitter::new(&client, &temp_dir("wrong"), 2023);
+
+        assert_eq!(
+            submitter.submit(2023, 3, 1, "42").unwrap(),
+            Outcome::Submitted(Response {
+                verdict: Verdict::TooLow,
+                wait: None
+            })
+        );
+        assert_eq!(
+            submitter.submit(2023, 3, 1, "42").unwrap(),
+            Outcome::KnownWrong("too low".into())
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2023/day/3/answer");
+        assert_eq!(requests[0].body, "level=1&answer=42");
+    }
+
+    #[test]
+    fn waits_before_submitting_again() {
+        let server = StubServer::start(|_| {
+            (
+                200,
+                page("That's not the right answer. Please wait one minute before trying again."),
+            )
+        });
+        let client = Client::new(server.url(), Some("abc".into()));
+        let submitter = Submitter::new(&client, &temp_dir("wait"), 2023);
+
+        submitter.submit(2023, 3, 1, "42").unwrap();
+        assert!(matches!(
+            submitter.submit(2023, 3, 1, "43").unwrap(),
+            Outcome::MustWait(wait) if wait <= Duration::from_secs(60)
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt::Display;
+
+/// The answer to one part of a puzzle.
+///
+/// Integers are always stored in the smallest variant that fits them, so answers that are equal
+/// compare equal no matter which integer type the solver produced them from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    Text(String),
+    /// A multi-line answer such as letters drawn on a grid, one entry per row.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    fn from_i128(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(v) => Answer::Integer(v),
+            Err(_) => Answer::BigInteger(value),
+        }
+    }
+
+    /// The answer as an integer, if it is one.
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(v) => Some(*v as i128),
+            Answer::BigInteger(v) => Some(*v),
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(v) => write!(f, "{v}"),
+            Answer::BigInteger(v) => write!(f, "{v}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::from_i128(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
@@ -0,0 +1,250 @@
+//! Finding where a sequence of states, each computed from the one before, starts repeating.
+
+use std::{collections::HashMap, hash::Hash};
+
+use crate::modular::{crt, Congruence};
+
+/// Where a sequence of states repeats: from step `offset` on, every state comes back `period`
+/// steps later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub offset: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as step `n`'s.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.offset {
+            n
+        } else {
+            self.offset + (n - self.offset) % self.period
+        }
+    }
+}
+
+/// Finds the cycle in the states reached by applying `step` over and over to `initial`, using
+/// Brent's algorithm so that only a couple of states are kept at a time.
+///
+/// Never returns if the states don't repeat.
+pub fn find_cycle<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // Find the period by letting the hare run ahead of the tortoise in growing powers of two.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // With the hare a period ahead, both meet at the first repeated state.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+
+    Cycle { offset, period }
+}
+
+/// Like [`find_cycle`], but remembers every state in a hash map, taking only as many steps as it
+/// takes for a state to repeat.
+///
+/// Returns the states before the first repeat as well, so `states[cycle.equivalent_step(n)]` is
+/// the state after `n` steps.
+pub fn find_cycle_hashed<S: Clone + Eq + Hash>(
+    initial: S,
+    step: impl FnMut(&S) -> S,
+) -> (Cycle, Vec<S>) {
+    find_cycle_hashed_within(initial, usize::MAX, step).unwrap()
+}
+
+/// Like [`find_cycle_hashed`], but gives up with `None` if no state has repeated after
+/// `max_steps` steps.
+pub fn find_cycle_hashed_within<S: Clone + Eq + Hash>(
+    initial: S,
+    max_steps: usize,
+    mut step: impl FnMut(&S) -> S,
+) -> Option<(Cycle, Vec<S>)> {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(&offset) = seen.get(&state) {
+            let cycle = Cycle {
+                offset,
+                period: states.len() - offset,
+            };
+            return Some((cycle, states));
+        }
+        if states.len() == max_steps {
+            return None;
+        }
+
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Whether a condition holds at each step of a sequence that cycles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Periodic {
+    pub cycle: Cycle,
+    /// Whether it holds at each step before the cycle first repeats.
+    pub holds: Vec<bool>,
+}
+
+impl Periodic {
+    pub fn holds_at(&self, step: usize) -> bool {
+        self.holds[self.cycle.equivalent_step(step)]
+    }
+
+    /// The steps it holds at once in its cycle.
+    fn in_cycle(&self) -> impl Iterator<Item = Congruence> + '_ {
+        (self.cycle.offset..self.holds.len())
+            .filter(|&step| self.holds[step])
+            .map(|step| Congruence::new(step as i128, self.cycle.period as i128))
+    }
+}
+
+/// The first step at which every condition holds, or `None` if they never all do or there are
+/// none to hold.
+///
+/// Checks each step in turn until every sequence is in its cycle, then solves for the steps at
+/// which every cycle holds at once with the Chinese remainder theorem.
+pub fn first_common_step(conditions: &[Periodic]) -> Option<usize> {
+    if conditions.is_empty() {
+        return None;
+    }
+
+    let all_cycling = conditions
+        .iter()
+        .map(|condition| condition.cycle.offset)
+        .max()
+        .unwrap_or(0);
+    if let Some(step) =
+        (0..all_cycling).find(|&step| conditions.iter().all(|condition| condition.holds_at(step)))
+    {
+        return Some(step);
+    }
+
+    let mut together = vec![Congruence::new(0, 1)];
+    for condition in conditions {
+        together = together
+            .iter()
+            .flat_map(|&steps| condition.in_cycle().filter_map(move |end| crt(steps, end)))
+            .collect();
+    }
+
+    together
+        .iter()
+        .map(|steps| steps.first_at_least(all_cycling as i128) as usize)
+        .min()
+}
+
+/// The state after applying `step` to `initial` `n` times, skipping the repeats so that `n` can
+/// be as large as a billion.
+pub fn state_at<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let cycle = find_cycle(initial.clone(), &mut step);
+
+    let mut state = initial;
+    for _ in 0..cycle.equivalent_step(n) {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        find_cycle, find_cycle_hashed, find_cycle_hashed_within, first_common_step, state_at,
+        Cycle, Periodic,
+    };
+
+    // 3, 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+    fn collatz(n: &u64) -> u64 {
+        if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        }
+    }
+
+    #[test]
+    fn finds_offset_and_period() {
+        let expected = Cycle {
+            offset: 5,
+            period: 3,
+        };
+
+        assert_eq!(find_cycle(3, collatz), expected);
+        let (cycle, states) = find_cycle_hashed(3, collatz);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, [3, 10, 5, 16, 8, 4, 2, 1]);
+
+        assert_eq!(
+            find_cycle(0, |n| (n + 1) % 4),
+            Cycle {
+                offset: 0,
+                period: 4
+            }
+        );
+        assert_eq!(find_cycle_hashed_within(3, 7, collatz), None);
+        assert_eq!(find_cycle_hashed_within(3, 8, collatz).unwrap().0, expected);
+    }
+
+    #[test]
+    fn common_steps() {
+        // Every other step from step 2, and every third step from step 1.
+        let evens = Periodic {
+            cycle: Cycle {
+                offset: 1,
+                period: 2,
+            },
+            holds: vec![false, false, true],
+        };
+        let thirds = Periodic {
+            cycle: Cycle {
+                offset: 0,
+                period: 3,
+            },
+            holds: vec![false, true, false],
+        };
+        assert_eq!(first_common_step(&[evens.clone(), thirds]), Some(4));
+
+        let odds = Periodic {
+            cycle: Cycle {
+                offset: 0,
+                period: 2,
+            },
+            holds: vec![false, true],
+        };
+        assert_eq!(first_common_step(&[evens, odds]), None);
+        assert_eq!(first_common_step(&[]), None);
+    }
+
+    #[test]
+    fn state_at_matches_stepping() {
+        let mut state = 3;
+        for n in 0..20 {
+            assert_eq!(state_at(3, collatz, n), state);
+            state = collatz(&state);
+        }
+        assert_eq!(state_at(3, collatz, 1_000_000_000), 1);
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt::Display;
+
+/// One of the four directions on a grid, where up is towards the first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise starting from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Parses the letters `U`, `R`, `D` and `L`.
+    pub fn from_letter(c: char) -> Option<Direction> {
+        match c {
+            'U' => Some(Direction::Up),
+            'R' => Some(Direction::Right),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Parses the arrows `^`, `>`, `v` and `<`.
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Parses the digits `0` to `3` as right, down, left and up, as in day 18's hex codes.
+    pub fn from_digit(c: char) -> Option<Direction> {
+        match c {
+            '0' => Some(Direction::Right),
+            '1' => Some(Direction::Down),
+            '2' => Some(Direction::Left),
+            '3' => Some(Direction::Up),
+            _ => None,
+        }
+    }
+
+    /// The arrow pointing this way, the inverse of [`Direction::from_arrow`].
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// The change in column and row when taking a step this way.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+
+    /// The direction a beam going this way leaves a `/` mirror in.
+    pub fn reflect_slash(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    /// The direction a beam going this way leaves a `\` mirror in.
+    pub fn reflect_backslash(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Up,
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Direction::Up => "up",
+            Direction::Right => "right",
+            Direction::Down => "down",
+            Direction::Left => "left",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Direction;
+
+    #[test]
+    fn turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_eq!(Direction::from_arrow(dir.arrow()), Some(dir));
+        }
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn reflections() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.reflect_slash().reflect_slash(), dir);
+            assert_eq!(dir.reflect_backslash().reflect_backslash(), dir);
+        }
+        assert_eq!(Direction::Right.reflect_slash(), Direction::Up);
+        assert_eq!(Direction::Right.reflect_backslash(), Direction::Down);
+    }
+}
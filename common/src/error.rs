@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+/// A failure to solve a part for an input that parsed fine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input has no answer, e.g. there is no path to the goal.
+    Unsolvable(String),
+    /// The input doesn't have the shape the solver relies on.
+    UnsupportedInputShape(String),
+    /// The part has no solver.
+    NotImplemented,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Unsolvable(reason) => write!(f, "unsolvable: {reason}"),
+            SolveError::UnsupportedInputShape(reason) => {
+                write!(f, "unsupported input shape: {reason}")
+            }
+            SolveError::NotImplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
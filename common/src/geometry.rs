@@ -0,0 +1,3 @@
+//! Shapes on the integer lattice.
+
+pub mod polygon;
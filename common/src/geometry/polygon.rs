@@ -0,0 +1,118 @@
+//! Simple polygons whose corners lie on the integer lattice, given as their vertices in order
+//! around the edge. The last vertex connects back to the first.
+
+/// A vertex as `(x, y)`.
+pub type Vertex = (i64, i64);
+
+/// The edges of the polygon, each from one vertex to the next.
+fn edges(vertices: &[Vertex]) -> impl Iterator<Item = (Vertex, Vertex)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Twice the area by the shoelace formula, which is always a whole number for lattice polygons.
+///
+/// It is positive when the vertices go clockwise on a grid where `y` points down, and negative
+/// when they go counterclockwise.
+pub fn double_signed_area(vertices: &[Vertex]) -> i64 {
+    edges(vertices)
+        .map(|((ax, ay), (bx, by))| ax * by - bx * ay)
+        .sum()
+}
+
+/// The number of lattice points on the edge.
+pub fn boundary_points(vertices: &[Vertex]) -> i64 {
+    edges(vertices)
+        .map(|((ax, ay), (bx, by))| gcd((bx - ax).abs(), (by - ay).abs()))
+        .sum()
+}
+
+/// The number of lattice points strictly inside, by Pick's theorem: `A = I + B / 2 - 1`.
+pub fn interior_points(vertices: &[Vertex]) -> i64 {
+    (double_signed_area(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+/// Whether `p` lies on the edge.
+pub fn on_boundary(vertices: &[Vertex], p: Vertex) -> bool {
+    edges(vertices).any(|(a, b)| {
+        let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+        cross == 0
+            && a.0.min(b.0) <= p.0
+            && p.0 <= a.0.max(b.0)
+            && a.1.min(b.1) <= p.1
+            && p.1 <= a.1.max(b.1)
+    })
+}
+
+/// Whether `p` lies inside or on the edge.
+pub fn contains(vertices: &[Vertex], p: Vertex) -> bool {
+    if on_boundary(vertices, p) {
+        return true;
+    }
+
+    // Cast a ray to the right and count the edges it crosses, counting an edge's lower end but
+    // not its upper one so a ray through a vertex isn't counted twice.
+    let crossings = edges(vertices)
+        .filter(|&(a, b)| (a.1 > p.1) != (b.1 > p.1))
+        .filter(|&(a, b)| {
+            // Compare `p.0` with where the edge crosses `y = p.1`, without dividing.
+            let lhs = (p.0 - a.0) as i128 * (b.1 - a.1) as i128;
+            let rhs = (p.1 - a.1) as i128 * (b.0 - a.0) as i128;
+            if b.1 > a.1 {
+                lhs < rhs
+            } else {
+                lhs > rhs
+            }
+        })
+        .count();
+    crossings % 2 == 1
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{boundary_points, contains, double_signed_area, interior_points, on_boundary};
+
+    #[test]
+    fn square() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+
+        assert_eq!(double_signed_area(&square), 32);
+        let reversed: Vec<_> = square.iter().rev().copied().collect();
+        assert_eq!(double_signed_area(&reversed), -32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        assert_eq!(interior_points(&reversed), 9);
+    }
+
+    #[test]
+    fn point_in_polygon() {
+        // An L shape with a notch cut out of the top right.
+        let l_shape = [(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)];
+
+        assert!(contains(&l_shape, (1, 1)));
+        assert!(contains(&l_shape, (3, 3)));
+        assert!(!contains(&l_shape, (3, 1)));
+        assert!(!contains(&l_shape, (5, 2)));
+        assert!(on_boundary(&l_shape, (2, 1)));
+        assert!(contains(&l_shape, (2, 1)));
+        assert!(!on_boundary(&l_shape, (1, 1)));
+        assert_eq!(interior_points(&l_shape), 5);
+        assert_eq!(
+            (0..=4)
+                .flat_map(|x| (0..=4).map(move |y| (x, y)))
+                .filter(|&p| contains(&l_shape, p) && !on_boundary(&l_shape, p))
+                .count(),
+            5
+        );
+    }
+}
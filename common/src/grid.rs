@@ -0,0 +1,292 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::Direction;
+
+/// A position on a grid, with `x` counting columns from the left and `y` rows from the top.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// The point `dx` columns and `dy` rows away, or `None` if that's left of or above the origin.
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid of cells, such as a puzzle's map, stored row by row.
+///
+/// Parse one from text with [`Source::grid`](crate::Source::grid).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row order, panicking if there aren't `width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "A {width}x{height} grid needs {} cells",
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from its rows, panicking if they aren't all as long as the first.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "Every row of a grid must be as long as the first"
+        );
+        Self::new(width, height, rows.into_iter().flatten().collect())
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.y * self.width + p.x])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.contains(p)
+            .then(|| &mut self.cells[p.y * self.width + p.x])
+    }
+
+    /// The point `dx` columns and `dy` rows away from `p`, or `None` if that's off the grid.
+    pub fn offset(&self, p: Point, dx: isize, dy: isize) -> Option<Point> {
+        p.offset(dx, dy).filter(|&next| self.contains(next))
+    }
+
+    /// The point a step from `p` in `dir`, or `None` if that's off the grid.
+    pub fn step(&self, p: Point, dir: Direction) -> Option<Point> {
+        let (dx, dy) = dir.delta();
+        self.offset(p, dx, dy)
+    }
+
+    /// The points above, right of, below and left of `p`, skipping those off the grid.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        NEIGHBOURS_4
+            .into_iter()
+            .filter_map(move |(dx, dy)| self.offset(p, dx, dy))
+    }
+
+    /// The points around `p`, diagonals included, skipping those off the grid.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .filter_map(move |(dx, dy)| self.offset(p, dx, dy))
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell of the grid along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The first point, row by row, whose cell matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {x} is off the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flat_map(|column| column.cloned()).collect();
+        Grid::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid a quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self
+            .columns()
+            .flat_map(|column| column.cloned().collect::<Vec<T>>().into_iter().rev())
+            .collect();
+        Grid::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise, so the top row becomes the left column.
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p} is off the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p} is off the {width}x{height} grid"))
+    }
+}
+
+/// Prints the cells of every row next to each other, with a line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                cell.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, Point};
+
+    fn grid() -> Grid<char> {
+        Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']])
+    }
+
+    #[test]
+    fn rotations_and_transpose() {
+        assert_eq!(grid().rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid().rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid().transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(
+            grid()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid()
+        );
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.position(|&c| c == 'e'), Some(Point::new(1, 1)));
+    }
+}
@@ -0,0 +1,266 @@
+//! Sets of integers, or of points with integer coordinates, stored as ranges rather than one
+//! value at a time.
+
+/// The integers from `start` up to but not including `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// The integers from `first` to `last`, both included.
+    pub const fn inclusive(first: i64, last: i64) -> Self {
+        Self::new(first, last + 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    pub fn intersect(&self, other: &Interval) -> Interval {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The integers below `x` and those from `x` on, either of which may be empty.
+    pub fn split_at(&self, x: i64) -> (Interval, Interval) {
+        let x = x.max(self.start).min(self.end);
+        (Interval::new(self.start, x), Interval::new(x, self.end))
+    }
+
+    pub fn translate(&self, by: i64) -> Interval {
+        Interval::new(self.start + by, self.end + by)
+    }
+}
+
+/// The points whose every coordinate lies in the matching side, e.g. a rectangle for `N = 2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HyperRect<const N: usize> {
+    pub sides: [Interval; N],
+}
+
+impl<const N: usize> HyperRect<N> {
+    pub const fn new(sides: [Interval; N]) -> Self {
+        Self { sides }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sides.iter().any(Interval::is_empty)
+    }
+
+    /// The number of points inside.
+    pub fn volume(&self) -> i64 {
+        self.sides.iter().map(Interval::len).product()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.sides
+            .iter()
+            .zip(point)
+            .all(|(side, x)| side.contains(x))
+    }
+
+    pub fn intersect(&self, other: &HyperRect<N>) -> HyperRect<N> {
+        HyperRect::new(std::array::from_fn(|dim| {
+            self.sides[dim].intersect(&other.sides[dim])
+        }))
+    }
+
+    /// The points whose coordinate `dim` is below `x` and those where it's `x` or more.
+    pub fn split_at(&self, dim: usize, x: i64) -> (HyperRect<N>, HyperRect<N>) {
+        let (below, from) = self.sides[dim].split_at(x);
+        let (mut lower, mut upper) = (*self, *self);
+        lower.sides[dim] = below;
+        upper.sides[dim] = from;
+        (lower, upper)
+    }
+
+    pub fn translate(&self, by: [i64; N]) -> HyperRect<N> {
+        HyperRect::new(std::array::from_fn(|dim| {
+            self.sides[dim].translate(by[dim])
+        }))
+    }
+
+    /// The points outside `other`, as at most `2 * N` disjoint pieces.
+    pub fn subtract(&self, other: &HyperRect<N>) -> Vec<HyperRect<N>> {
+        let overlap = self.intersect(other);
+        if overlap.is_empty() {
+            return [*self]
+                .into_iter()
+                .filter(|rect| !rect.is_empty())
+                .collect();
+        }
+
+        // Slice off what's below and above the overlap one dimension at a time.
+        let mut pieces = vec![];
+        let mut rest = *self;
+        for dim in 0..N {
+            let (below, from) = rest.split_at(dim, overlap.sides[dim].start);
+            let (inside, above) = from.split_at(dim, overlap.sides[dim].end);
+            pieces.extend([below, above].into_iter().filter(|rect| !rect.is_empty()));
+            rest = inside;
+        }
+        pieces
+    }
+}
+
+/// Any set of points in `N` dimensions, stored as disjoint [`HyperRect`]s. For `N = 1` that's a
+/// set of integers stored as disjoint intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<const N: usize> {
+    rects: Vec<HyperRect<N>>,
+}
+
+impl<const N: usize> IntervalSet<N> {
+    pub fn new() -> Self {
+        Self { rects: vec![] }
+    }
+
+    pub fn rects(&self) -> impl Iterator<Item = &HyperRect<N>> {
+        self.rects.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// The number of points in the set.
+    pub fn volume(&self) -> i64 {
+        self.rects.iter().map(HyperRect::volume).sum()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.rects.iter().any(|rect| rect.contains(point))
+    }
+
+    /// Adds the points of `rect` that aren't in the set yet.
+    pub fn insert(&mut self, rect: HyperRect<N>) {
+        let mut pieces = vec![rect];
+        for existing in &self.rects {
+            pieces = pieces
+                .iter()
+                .flat_map(|piece| piece.subtract(existing))
+                .collect();
+        }
+        self.rects
+            .extend(pieces.into_iter().filter(|rect| !rect.is_empty()));
+    }
+
+    pub fn union(&self, other: &IntervalSet<N>) -> IntervalSet<N> {
+        let mut union = self.clone();
+        for &rect in &other.rects {
+            union.insert(rect);
+        }
+        union
+    }
+
+    pub fn intersect(&self, other: &IntervalSet<N>) -> IntervalSet<N> {
+        let rects = self
+            .rects
+            .iter()
+            .flat_map(|a| other.rects.iter().map(|b| a.intersect(b)))
+            .filter(|rect| !rect.is_empty())
+            .collect();
+        IntervalSet { rects }
+    }
+
+    pub fn subtract(&self, other: &IntervalSet<N>) -> IntervalSet<N> {
+        let mut rects = self.rects.clone();
+        for removed in &other.rects {
+            rects = rects
+                .iter()
+                .flat_map(|rect| rect.subtract(removed))
+                .collect();
+        }
+        IntervalSet { rects }
+    }
+
+    /// The points in `dim` below `x` and those from `x` on.
+    pub fn split_at(&self, dim: usize, x: i64) -> (IntervalSet<N>, IntervalSet<N>) {
+        let (below, from): (Vec<_>, Vec<_>) =
+            self.rects.iter().map(|rect| rect.split_at(dim, x)).unzip();
+        let keep = |rects: Vec<HyperRect<N>>| IntervalSet {
+            rects: rects.into_iter().filter(|rect| !rect.is_empty()).collect(),
+        };
+        (keep(below), keep(from))
+    }
+
+    pub fn translate(&self, by: [i64; N]) -> IntervalSet<N> {
+        IntervalSet {
+            rects: self.rects.iter().map(|rect| rect.translate(by)).collect(),
+        }
+    }
+}
+
+impl<const N: usize> From<HyperRect<N>> for IntervalSet<N> {
+    fn from(rect: HyperRect<N>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(rect);
+        set
+    }
+}
+
+impl<const N: usize> FromIterator<HyperRect<N>> for IntervalSet<N> {
+    fn from_iter<I: IntoIterator<Item = HyperRect<N>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for rect in iter {
+            set.insert(rect);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HyperRect, Interval, IntervalSet};
+
+    fn square(x: i64, y: i64, size: i64) -> HyperRect<2> {
+        HyperRect::new([Interval::new(x, x + size), Interval::new(y, y + size)])
+    }
+
+    #[test]
+    fn intervals() {
+        let set: IntervalSet<1> = [
+            HyperRect::new([Interval::new(0, 10)]),
+            HyperRect::new([Interval::new(5, 15)]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.volume(), 15);
+
+        let middle = IntervalSet::from(HyperRect::new([Interval::inclusive(3, 6)]));
+        assert_eq!(set.intersect(&middle).volume(), 4);
+        assert_eq!(set.subtract(&middle).volume(), 11);
+        assert!(!set.subtract(&middle).contains([4]));
+
+        let (below, from) = set.split_at(0, 12);
+        assert_eq!((below.volume(), from.volume()), (12, 3));
+        assert!(set.translate([-20]).contains([-6]));
+    }
+
+    #[test]
+    fn rectangles() {
+        let big = square(0, 0, 4);
+        let pieces = big.subtract(&square(1, 1, 2));
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(pieces.iter().map(HyperRect::volume).sum::<i64>(), 12);
+
+        let set = IntervalSet::from(big).union(&square(2, 2, 4).into());
+        assert_eq!(set.volume(), 16 + 16 - 4);
+        assert_eq!(set.intersect(&square(3, 3, 1).into()).volume(), 1);
+        assert!(set.contains([5, 5]) && !set.contains([5, 0]));
+        assert!(IntervalSet::from(big).subtract(&set).is_empty());
+    }
+}
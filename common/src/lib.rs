@@ -0,0 +1,19 @@
+pub mod answer;
+pub mod cycle;
+pub mod direction;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod interval;
+pub mod modular;
+pub mod parse;
+pub mod search;
+pub mod solution;
+pub mod svg;
+
+pub use answer::Answer;
+pub use direction::Direction;
+pub use error::SolveError;
+pub use grid::{Grid, Point};
+pub use parse::{ParseError, Source};
+pub use solution::Solution;
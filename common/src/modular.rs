@@ -0,0 +1,82 @@
+//! Congruences, for finding when several things that repeat with different periods line up.
+
+/// The numbers `x` with `x ≡ residue (mod modulus)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Congruence {
+    pub residue: i128,
+    pub modulus: i128,
+}
+
+impl Congruence {
+    /// Builds a congruence with its residue reduced to `0..modulus`, panicking if the modulus
+    /// isn't positive.
+    pub fn new(residue: i128, modulus: i128) -> Self {
+        assert!(modulus > 0, "A modulus must be positive, not {modulus}");
+        Self {
+            residue: residue.rem_euclid(modulus),
+            modulus,
+        }
+    }
+
+    pub fn contains(&self, x: i128) -> bool {
+        x.rem_euclid(self.modulus) == self.residue
+    }
+
+    /// The smallest number in the congruence that is at least `n`.
+    pub fn first_at_least(&self, n: i128) -> i128 {
+        n + (self.residue - n).rem_euclid(self.modulus)
+    }
+}
+
+/// The numbers in both `a` and `b`, or `None` if there are none.
+///
+/// This is the Chinese remainder theorem generalised to moduli that aren't coprime, so the
+/// result's modulus is the least common multiple of theirs.
+pub fn crt(a: Congruence, b: Congruence) -> Option<Congruence> {
+    let (gcd, inverse, _) = extended_gcd(a.modulus, b.modulus);
+    let difference = b.residue - a.residue;
+    if difference % gcd != 0 {
+        return None;
+    }
+
+    // Find k with a.residue + k * a.modulus ≡ b.residue (mod b.modulus).
+    let step = b.modulus / gcd;
+    let k = (difference / gcd % step * inverse).rem_euclid(step);
+
+    Some(Congruence::new(a.residue + k * a.modulus, a.modulus * step))
+}
+
+/// The greatest common divisor `g` of `a` and `b` along with `x` and `y` such that
+/// `a * x + b * y = g`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crt, Congruence};
+
+    #[test]
+    fn coprime_moduli() {
+        let x = crt(Congruence::new(2, 3), Congruence::new(3, 5)).unwrap();
+        let x = crt(x, Congruence::new(2, 7)).unwrap();
+
+        assert_eq!(x, Congruence::new(23, 105));
+    }
+
+    #[test]
+    fn shared_factors() {
+        assert_eq!(
+            crt(Congruence::new(2, 4), Congruence::new(4, 6)),
+            Some(Congruence::new(10, 12))
+        );
+        assert_eq!(crt(Congruence::new(1, 4), Congruence::new(2, 6)), None);
+        assert_eq!(Congruence::new(10, 12).first_at_least(11), 22);
+        assert!(Congruence::new(-2, 12).contains(22));
+    }
+}
@@ -0,0 +1,172 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::grid::Grid;
+
+/// A failure to parse the puzzle input, pointing at where in the input it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-indexed line of the input the error occurred on.
+    pub line: usize,
+    /// The 1-indexed column, counted in characters, the error occurred at.
+    pub column: usize,
+    /// What the parser expected to find at that position.
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Renders the error followed by the offending line of `input` with a caret under the column.
+    pub fn render(&self, input: &str) -> String {
+        let line = input.lines().nth(self.line.saturating_sub(1)).unwrap_or("");
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let indent = " ".repeat(self.column.saturating_sub(1));
+
+        format!("error: {self}\n{gutter} |\n{number} | {line}\n{gutter} | {indent}^")
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected {} at line {}, column {}",
+            self.expected, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The complete puzzle input, used to report errors at the position of a slice of it.
+///
+/// The helpers take slices of the source text and return slices of it, so every piece a parser
+/// splits off can still be located when something about it turns out to be wrong.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    /// Creates an error located at the start of `at`, which should be a slice of the source text.
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        let mut offset = (at.as_ptr() as usize)
+            .saturating_sub(self.text.as_ptr() as usize)
+            .min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+
+        ParseError::new(line, column, expected)
+    }
+
+    /// Creates an error located right after the end of `s`.
+    pub fn error_after(&self, s: &str, expected: impl Into<String>) -> ParseError {
+        self.error(&s[s.len()..], expected)
+    }
+
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("`{prefix}`")))
+    }
+
+    pub fn strip_suffix(&self, s: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+        s.strip_suffix(suffix)
+            .ok_or_else(|| self.error_after(s, format!("`{suffix}`")))
+    }
+
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter)
+            .ok_or_else(|| self.error_after(s, format!("`{}`", delimiter.escape_debug())))
+    }
+
+    pub fn number<T: FromStr>(&self, s: &'a str) -> Result<T, ParseError> {
+        s.parse().map_err(|_| self.error(s, "a number"))
+    }
+
+    /// Splits `s` into a grid, turning every character into a cell with `cell`.
+    ///
+    /// `cell` returns `None` for characters that don't belong in the grid, which are reported as
+    /// not being the `expected` ones, and every line must be as long as the first. The grid must
+    /// have at least one cell, so that its corners exist.
+    pub fn grid<T>(
+        &self,
+        s: &'a str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for l in s.lines() {
+            let mut row_width = 0;
+            for (i, c) in l.char_indices() {
+                if width == Some(row_width) {
+                    return Err(self.error(&l[i..], "the end of the row"));
+                }
+                cells.push(cell(c).ok_or_else(|| self.error(&l[i..], expected))?);
+                row_width += 1;
+            }
+
+            let width = *width.get_or_insert(row_width);
+            if width == 0 {
+                return Err(self.error(l, expected));
+            }
+            if row_width < width {
+                return Err(self.error_after(l, format!("a row of {width} cells")));
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) => Ok(Grid::new(width, height, cells)),
+            None => Err(self.error(s, expected)),
+        }
+    }
+
+    /// Splits `s` into a grid of characters, rejecting any not in `allowed`.
+    pub fn char_grid(&self, s: &'a str, allowed: &str) -> Result<Grid<char>, ParseError> {
+        self.grid(s, &format!("one of `{allowed}`"), |c| {
+            allowed.contains(c).then_some(c)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, Source};
+
+    #[test]
+    fn grids_must_not_be_empty() {
+        let grid = |s| Source::new(s).char_grid(s, ".#").map(|grid| grid.width());
+
+        assert_eq!(grid(".#\n#."), Ok(2));
+        assert_eq!(grid(""), Err(ParseError::new(1, 1, "one of `.#`")));
+        assert_eq!(grid("\n.#"), Err(ParseError::new(1, 1, "one of `.#`")));
+        assert_eq!(
+            grid(".#\n#"),
+            Err(ParseError::new(2, 2, "a row of 2 cells"))
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Graph searches over any state type, with the graph given as a function from a state to its
+//! neighbours.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// How much work a search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States taken off the frontier and expanded.
+    pub visited: usize,
+    /// States put on the frontier, counting a state again whenever a cheaper way to it is found.
+    pub discovered: usize,
+}
+
+/// The cheapest way a search found from a start to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<S, C> {
+    pub cost: C,
+    /// Every state along the way, from the start to the goal.
+    pub path: Vec<S>,
+    pub stats: Stats,
+}
+
+/// The states a search has seen, each with the index of the state it was reached from.
+struct Tree<S> {
+    states: Vec<S>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<S: Clone> Tree<S> {
+    fn new() -> Self {
+        Self {
+            states: vec![],
+            parents: vec![],
+        }
+    }
+
+    fn push(&mut self, state: S, parent: Option<usize>) -> usize {
+        self.states.push(state);
+        self.parents.push(parent);
+        self.states.len() - 1
+    }
+
+    fn path_to(&self, mut index: usize) -> Vec<S> {
+        let mut path = vec![self.states[index].clone()];
+        while let Some(parent) = self.parents[index] {
+            path.push(self.states[parent].clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Finds the fewest steps from any of `starts` to a state matching `is_goal`.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut tree = Tree::new();
+    let mut seen = HashSet::new();
+    let mut frontier = VecDeque::new();
+    let mut stats = Stats::default();
+
+    for start in starts {
+        if seen.insert(start.clone()) {
+            frontier.push_back((tree.push(start, None), 0));
+            stats.discovered += 1;
+        }
+    }
+
+    while let Some((index, steps)) = frontier.pop_front() {
+        stats.visited += 1;
+        if is_goal(&tree.states[index]) {
+            return Some(Found {
+                cost: steps,
+                path: tree.path_to(index),
+                stats,
+            });
+        }
+
+        for next in neighbours(&tree.states[index]) {
+            if seen.insert(next.clone()) {
+                frontier.push_back((tree.push(next, Some(index)), steps + 1));
+                stats.discovered += 1;
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the fewest steps from any of `starts` to every state reachable from them.
+pub fn reachable<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut steps = HashMap::new();
+    let mut frontier = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = steps.entry(start.clone()) {
+            entry.insert(0);
+            frontier.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, n)) = frontier.pop_front() {
+        for next in neighbours(&state) {
+            if let Entry::Vacant(entry) = steps.entry(next.clone()) {
+                entry.insert(n + 1);
+                frontier.push_back((next, n + 1));
+            }
+        }
+    }
+
+    steps
+}
+
+/// Finds the cheapest way from any of `starts` to a state matching `is_goal`, where `neighbours`
+/// gives every state reachable from one along with the cost of getting there.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but expands the states that `heuristic` estimates to be closest to a goal
+/// first.
+///
+/// The heuristic must never overestimate the remaining cost for the found way to be the cheapest.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut tree = Tree::new();
+    // The cheapest known cost of every state, and the index in the tree it was reached with.
+    let mut best: HashMap<S, (C, usize)> = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+    let mut stats = Stats::default();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = best.entry(start.clone()) {
+            let estimate = heuristic(&start);
+            let index = tree.push(start, None);
+            entry.insert((C::default(), index));
+            frontier.push(Reverse((estimate, index)));
+            stats.discovered += 1;
+        }
+    }
+
+    while let Some(Reverse((_, index))) = frontier.pop() {
+        let state = &tree.states[index];
+        let (cost, best_index) = best[state];
+        if best_index != index {
+            // A cheaper way to this state was found after this one was put on the frontier.
+            continue;
+        }
+
+        stats.visited += 1;
+        if is_goal(state) {
+            return Some(Found {
+                cost,
+                path: tree.path_to(index),
+                stats,
+            });
+        }
+
+        for (next, step_cost) in neighbours(&state.clone()) {
+            let next_cost = cost + step_cost;
+            if best
+                .get(&next)
+                .is_some_and(|&(known, _)| known <= next_cost)
+            {
+                continue;
+            }
+
+            let estimate = next_cost + heuristic(&next);
+            let next_index = tree.push(next.clone(), Some(index));
+            best.insert(next, (next_cost, next_index));
+            frontier.push(Reverse((estimate, next_index)));
+            stats.discovered += 1;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, bfs, dijkstra, reachable};
+    use crate::{Grid, Point};
+
+    fn maze() -> Grid<char> {
+        let rows = ["..#....", ".##.##.", "....#..", "#.#...#", "..#.#.."];
+        Grid::from_rows(rows.iter().map(|row| row.chars().collect()).collect())
+    }
+
+    #[test]
+    fn searches_agree_on_a_maze() {
+        let maze = maze();
+        let start = Point::new(0, 0);
+        let goal = Point::new(6, 4);
+        let open = |p: &Point| {
+            maze.neighbours4(*p)
+                .filter(|&next| maze[next] == '.')
+                .collect::<Vec<Point>>()
+        };
+
+        let found = bfs([start], open, |p| *p == goal).unwrap();
+        assert_eq!(found.cost, 10);
+        assert_eq!(found.path.len(), 11);
+        assert_eq!(found.path.first(), Some(&start));
+        assert_eq!(found.path.last(), Some(&goal));
+        assert!(found
+            .path
+            .windows(2)
+            .all(|step| step[0].manhattan_distance(step[1]) == 1));
+
+        let weighted = |p: &Point| open(p).into_iter().map(|next| (next, 1));
+        let by_dijkstra = dijkstra([start], weighted, |p| *p == goal).unwrap();
+        let by_astar = astar(
+            [start],
+            weighted,
+            |p| p.manhattan_distance(goal),
+            |p| *p == goal,
+        )
+        .unwrap();
+
+        assert_eq!(by_dijkstra.cost, 10);
+        assert_eq!(by_astar.cost, 10);
+        assert!(by_astar.stats.visited <= by_dijkstra.stats.visited);
+
+        assert_eq!(reachable([start], open)[&goal], 10);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let walled_in = |_: &Point| Vec::<(Point, u32)>::new();
+
+        assert_eq!(
+            dijkstra([Point::new(0, 0)], walled_in, |p| *p == Point::new(6, 4)),
+            None
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{Answer, ParseError, SolveError};
+
+/// The contract every day fulfills: parse the puzzle input once, then solve either part from it.
+///
+/// The parsed input is handed to the solver by value so that parts are free to consume or mutate it,
+/// which means the runner parses the input again for every part it runs.
+pub trait Solution {
+    /// The parsed form of the puzzle input, which may borrow from the input text.
+    type Parsed<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError>;
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError>;
+
+    /// Draws the input as the given part sees it, as an SVG image, or `None` for days that
+    /// have nothing worth drawing.
+    fn svg(_input: Self::Parsed<'_>, _part: u8) -> Option<Result<String, SolveError>> {
+        None
+    }
+
+    /// Writes the input as a Graphviz graph, or `None` for days whose input isn't a graph.
+    fn dot(_input: Self::Parsed<'_>) -> Option<String> {
+        None
+    }
+}
+
+/// Parses `input` and solves the given part of it, for checking the examples in tests.
+///
+/// Panics with the located error if `input` doesn't parse, since examples always should.
+pub fn solve_example<S: Solution>(part: u8, input: &str) -> Result<Answer, SolveError> {
+    let parsed = S::parse(input).unwrap_or_else(|err| panic!("{}", err.render(input)));
+    match part {
+        1 => S::solve_part_one(parsed),
+        2 => S::solve_part_two(parsed),
+        p => panic!("Invalid part {p}"),
+    }
+}
@@ -0,0 +1,107 @@
+//! Drawing puzzles as SVG images, for shapes too big to look at as printed text.
+
+use std::fmt::Display;
+
+/// How many pixels wide or high, whichever is larger, a drawing is shown at.
+const SIZE: f64 = 1000.0;
+
+/// A drawing in puzzle coordinates, with `y` pointing down as on a grid.
+///
+/// It's scaled to fit whatever it contains once written out, and lines keep their width in
+/// pixels at any scale.
+#[derive(Debug, Clone, Default)]
+pub struct Svg {
+    /// The smallest and largest `x` and `y` drawn at.
+    bounds: Option<((f64, f64), (f64, f64))>,
+    elements: Vec<String>,
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn include(&mut self, (x, y): (f64, f64)) {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds.unwrap_or(((x, y), (x, y)));
+        self.bounds = Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))));
+    }
+
+    /// A line `width` pixels wide.
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), colour: &str, width: f64) {
+        self.include(from);
+        self.include(to);
+        self.elements.push(format!(
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{colour}" stroke-width="{width}" stroke-linecap="square" vector-effect="non-scaling-stroke"/>"#,
+            from.0, from.1, to.0, to.1
+        ));
+    }
+
+    /// A closed shape through `points`, with `stroke` drawn a pixel wide around it.
+    pub fn polygon(&mut self, points: &[(f64, f64)], fill: &str, stroke: &str) {
+        for &p in points {
+            self.include(p);
+        }
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{x},{y}"))
+            .collect::<Vec<String>>()
+            .join(" ");
+        self.elements.push(format!(
+            r#"<polygon points="{points}" fill="{fill}" stroke="{stroke}" vector-effect="non-scaling-stroke"/>"#
+        ));
+    }
+
+    /// A filled rectangle from `corner` to `corner + size`.
+    pub fn rect(&mut self, corner: (f64, f64), size: (f64, f64), fill: &str) {
+        self.include(corner);
+        self.include((corner.0 + size.0, corner.1 + size.1));
+        self.elements.push(format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{fill}"/>"#,
+            corner.0, corner.1, size.0, size.1
+        ));
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds.unwrap_or(((0.0, 0.0), (1.0, 1.0)));
+        // Leave a margin so lines along the edge aren't cut in half.
+        let extent = (max_x - min_x).max(max_y - min_y).max(1.0);
+        let margin = extent / 50.0;
+        let (width, height) = (max_x - min_x + 2.0 * margin, max_y - min_y + 2.0 * margin);
+        let scale = SIZE / width.max(height);
+
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {width} {height}" width="{:.0}" height="{:.0}">"#,
+            min_x - margin,
+            min_y - margin,
+            width * scale,
+            height * scale
+        )?;
+        for element in &self.elements {
+            writeln!(f, "  {element}")?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Svg;
+
+    #[test]
+    fn scales_to_fit() {
+        let mut svg = Svg::new();
+        svg.line((0.0, 0.0), (100.0, 0.0), "red", 2.0);
+        svg.rect((0.0, 0.0), (10.0, 50.0), "#70c710");
+
+        let drawing = svg.to_string();
+        assert!(drawing.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 104 54" width="1000" height="519">"#
+        ));
+        assert!(drawing.contains(r#"stroke="red" stroke-width="2""#));
+        assert!(drawing.contains(r##"<rect x="0" y="0" width="10" height="50" fill="#70c710"/>"##));
+        assert!(drawing.ends_with("</svg>\n"));
+    }
+}
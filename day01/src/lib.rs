@@ -0,0 +1,52 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed<'a> = std::str::Lines<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, SolveError};
+
+    use super::Day01;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day01>(1, input), Ok(Answer::Integer(142)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input2.txt");
+        assert_eq!(solve_example::<Day01>(2, input), Ok(Answer::Integer(281)));
+    }
+
+    #[test]
+    fn lines_need_a_number() {
+        let input = "1abc2\neightwothree\n";
+        assert_eq!(
+            solve_example::<Day01>(1, input),
+            Err(SolveError::UnsupportedInputShape(
+                "`eightwothree` has no number in it".to_string()
+            ))
+        );
+        assert_eq!(solve_example::<Day01>(2, input), Ok(Answer::Integer(95)));
+    }
+}
@@ -1,5 +1,9 @@
-pub fn parse<'a>(input: &'a str) -> impl Iterator<Item = &'a str> + 'a {
-    input.lines()
+use std::str::Lines;
+
+use common::{Answer, ParseError, SolveError};
+
+pub fn parse(input: &str) -> Result<Lines<'_>, ParseError> {
+    Ok(input.lines())
 }
 
 const NUMBERS_MAP_1: [(&str, u32); 9] = [
@@ -14,34 +18,31 @@ const NUMBERS_MAP_1: [(&str, u32); 9] = [
     ("9", 9),
 ];
 
-pub fn solve_part_one<'a>(input: impl Iterator<Item = &'a str>) -> String {
-    input
-        .map(|l| {
-            let mut l = l;
-            let first = 'l: loop {
-                for (num, val) in NUMBERS_MAP_1.iter() {
-                    if l.starts_with(num) {
-                        break 'l val;
-                    }
-                }
-                // Move pointer one step forward.
-                l = &l[1..];
-            };
+/// The first and last of `numbers` in the line, as the two digits of a number.
+fn calibration_value(line: &str, numbers: &[(&str, u32)]) -> Result<u32, SolveError> {
+    let number_at = |(i, _): (usize, char)| {
+        numbers
+            .iter()
+            .find(|(num, _)| line[i..].starts_with(num))
+            .map(|&(_, val)| val)
+    };
+    // No number is spelled inside another, so the one that starts last also ends last.
+    let first = line.char_indices().find_map(number_at);
+    let last = line.char_indices().rev().find_map(number_at);
 
-            let second = 'l: loop {
-                for (num, val) in NUMBERS_MAP_1.iter() {
-                    if l.starts_with(num) {
-                        break 'l val;
-                    }
-                }
-                // Move pointer one step back.
-                l = &l[..l.len() - 1];
-            };
+    match (first, last) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(SolveError::UnsupportedInputShape(format!(
+            "`{line}` has no number in it"
+        ))),
+    }
+}
 
-            first * 10 + second
-        })
-        .sum::<u32>()
-        .to_string()
+pub fn solve_part_one<'a>(input: impl Iterator<Item = &'a str>) -> Result<Answer, SolveError> {
+    input
+        .map(|l| calibration_value(l, &NUMBERS_MAP_1))
+        .sum::<Result<u32, SolveError>>()
+        .map(Answer::from)
 }
 
 const NUMBERS_MAP_2: [(&str, u32); 18] = [
@@ -65,32 +66,9 @@ const NUMBERS_MAP_2: [(&str, u32); 18] = [
     ("nine", 9),
 ];
 
-pub fn solve_part_two<'a>(input: impl Iterator<Item = &'a str>) -> String {
+pub fn solve_part_two<'a>(input: impl Iterator<Item = &'a str>) -> Result<Answer, SolveError> {
     input
-        .map(|l| {
-            let mut l = l;
-            let first = 'l: loop {
-                for (num, val) in NUMBERS_MAP_2.iter() {
-                    if l.starts_with(num) {
-                        break 'l val;
-                    }
-                }
-                // Move pointer one step forward.
-                l = &l[1..];
-            };
-
-            let second = 'l: loop {
-                for (num, val) in NUMBERS_MAP_2.iter() {
-                    if l.ends_with(num) {
-                        break 'l val;
-                    }
-                }
-                // Move pointer one step back.
-                l = &l[..l.len() - 1];
-            };
-
-            first * 10 + second
-        })
-        .sum::<u32>()
-        .to_string()
+        .map(|l| calibration_value(l, &NUMBERS_MAP_2))
+        .sum::<Result<u32, SolveError>>()
+        .map(Answer::from)
 }
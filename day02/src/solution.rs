@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use common::{Answer, ParseError, Source};
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum Color {
     Red,
@@ -12,51 +14,53 @@ pub struct Game {
     sets: Vec<HashMap<Color, u32>>,
 }
 
-pub fn parse<'a>(input: &'a str) -> impl Iterator<Item = Game> + 'a {
-    input.lines().map(|l| {
-        let l = l
-            .strip_prefix("Game ")
-            .expect("Failed to strip prefix Game ");
-        let (id, sets) = l.split_once(": ").expect("Failed to split games with sets");
-        let id = id.parse::<u32>().expect("Failed to parse ID");
-
-        let sets = sets
-            .split("; ")
-            .map(|s| {
-                s.split(", ")
-                    .map(|entry| {
-                        let (count, color) = entry
-                            .split_once(" ")
-                            .expect("Failed to split on space in set");
-                        let color = match color {
-                            "green" => Color::Green,
-                            "red" => Color::Red,
-                            "blue" => Color::Blue,
-                            _ => panic!("Invalid color {color}"),
-                        };
-
-                        let count = count.parse::<u32>().expect("Failed to parse entry count");
-
-                        (color, count)
-                    })
-                    .collect::<HashMap<Color, u32>>()
-            })
-            .collect();
-
-        Game { id, sets }
-    })
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    let src = Source::new(input);
+
+    input
+        .lines()
+        .map(|l| {
+            let l = src.strip_prefix(l, "Game ")?;
+            let (id, sets) = src.split_once(l, ": ")?;
+            let id = src.number(id)?;
+
+            let sets = sets
+                .split("; ")
+                .map(|s| {
+                    s.split(", ")
+                        .map(|entry| {
+                            let (count, color) = src.split_once(entry, " ")?;
+                            let color = match color {
+                                "green" => Color::Green,
+                                "red" => Color::Red,
+                                "blue" => Color::Blue,
+                                _ => return Err(src.error(color, "`red`, `green` or `blue`")),
+                            };
+
+                            let count = src.number(count)?;
+
+                            Ok((color, count))
+                        })
+                        .collect::<Result<HashMap<Color, u32>, ParseError>>()
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(Game { id, sets })
+        })
+        .collect()
 }
 
 const POSSIBLE_RED: u32 = 12;
 const POSSIBLE_GREEN: u32 = 13;
 const POSSIBLE_BLUE: u32 = 14;
 
-pub fn solve_part_one<'a>(input: impl Iterator<Item = Game>) -> String {
+pub fn solve_part_one(input: Vec<Game>) -> Answer {
     input
-        .filter(|game| game_is_possible(game))
+        .into_iter()
+        .filter(game_is_possible)
         .map(|game| game.id)
         .sum::<u32>()
-        .to_string()
+        .into()
 }
 
 fn game_is_possible(game: &Game) -> bool {
@@ -69,11 +73,12 @@ fn game_is_possible(game: &Game) -> bool {
     })
 }
 
-pub fn solve_part_two<'a>(input: impl Iterator<Item = Game>) -> String {
+pub fn solve_part_two(input: Vec<Game>) -> Answer {
     input
+        .into_iter()
         .map(|game| power_of_min_number_of_cubes_for_sets(&game))
         .sum::<u32>()
-        .to_string()
+        .into()
 }
 
 fn power_of_min_number_of_cubes_for_sets(game: &Game) -> u32 {
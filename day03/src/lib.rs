@@ -0,0 +1,43 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed<'a> = solution::Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_one(input))
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_two(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer};
+
+    use super::Day03;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day03>(1, input), Ok(Answer::Integer(4361)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(
+            solve_example::<Day03>(2, input),
+            Ok(Answer::Integer(467835))
+        );
+    }
+}
@@ -1,66 +1,32 @@
 use std::collections::{HashMap, HashSet};
 
+use common::{Answer, Grid, ParseError, Point, Source};
+
 pub struct Map {
-    width: usize,
-    height: usize,
-    numbers: HashMap<(usize, usize), usize>,
-    symbols: HashSet<(usize, usize)>,
-    gears: HashSet<(usize, usize)>,
+    grid: Grid<char>,
+    numbers: HashMap<Point, usize>,
 }
 
 impl Map {
-    fn get_all_symbol_neighbours(&self) -> HashSet<(usize, usize)> {
-        let mut all_neighs = HashSet::new();
-
-        for (x, y) in self.symbols.iter() {
-            let start_x = if x == &0 { 0 } else { x - 1 };
-            let start_y = if y == &0 { 0 } else { y - 1 };
-
-            let end_x = if x == &(self.width - 1) {
-                self.width - 1
-            } else {
-                x + 1
-            };
-            let end_y = if y == &(self.height - 1) {
-                self.height - 1
-            } else {
-                y + 1
-            };
-
-            for in_y in start_y..=end_y {
-                for in_x in start_x..=end_x {
-                    all_neighs.insert((in_x, in_y));
-                }
-            }
-        }
+    fn symbols(&self) -> impl Iterator<Item = (Point, char)> + '_ {
+        self.grid
+            .iter()
+            .filter(|(_, c)| !c.is_ascii_digit() && **c != '.')
+            .map(|(p, c)| (p, *c))
+    }
 
-        all_neighs
+    fn get_all_symbol_neighbours(&self) -> HashSet<Point> {
+        self.symbols()
+            .flat_map(|(p, _)| self.grid.neighbours8(p))
+            .collect()
     }
 
-    fn get_gear_neighbours(&self) -> HashMap<(usize, usize), HashSet<(usize, usize)>> {
-        let mut all_neighs = HashMap::new();
-
-        for (x, y) in self.gears.iter() {
-            let start_x = if x == &0 { 0 } else { x - 1 };
-            let start_y = if y == &0 { 0 } else { y - 1 };
-
-            let end_x = if x == &(self.width - 1) {
-                self.width - 1
-            } else {
-                x + 1
-            };
-            let end_y = if y == &(self.height - 1) {
-                self.height - 1
-            } else {
-                y + 1
-            };
-
-            for in_y in start_y..=end_y {
-                for in_x in start_x..=end_x {
-                    let mut set = all_neighs.remove(&(in_x, in_y)).unwrap_or(HashSet::new());
-                    set.insert((*x, *y));
-                    all_neighs.insert((in_x, in_y), set);
-                }
+    fn get_gear_neighbours(&self) -> HashMap<Point, HashSet<Point>> {
+        let mut all_neighs: HashMap<Point, HashSet<Point>> = HashMap::new();
+
+        for (gear, _) in self.symbols().filter(|(_, c)| *c == '*') {
+            for p in self.grid.neighbours8(gear) {
+                all_neighs.entry(p).or_default().insert(gear);
             }
         }
 
@@ -68,109 +34,80 @@ impl Map {
     }
 }
 
-pub fn parse<'a>(input: &str) -> Map {
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    let grid = Source::new(input).grid(input, "any character", Some)?;
     let mut numbers = HashMap::new();
-    let mut symbols = HashSet::new();
-    let mut gears = HashSet::new();
-
-    let mut width = 0;
-    let height = input.lines().count();
 
-    for (y, line) in input.trim().lines().enumerate() {
+    for (y, row) in grid.rows().enumerate() {
         let mut num = String::new();
         let mut num_start = None;
-        width = line.len();
 
-        for (x, char) in line.chars().enumerate() {
-            if char.is_digit(10) {
-                num.push(char);
+        for (x, char) in row.iter().enumerate() {
+            if char.is_ascii_digit() {
+                num.push(*char);
 
                 if num_start.is_none() {
-                    num_start = Some((x, y));
-                }
-            } else {
-                if num.len() > 0 {
-                    numbers.insert(
-                        num_start.expect("No num start"),
-                        num.parse().expect("Invalid number"),
-                    );
-                    num = String::new();
-                    num_start = None;
-                }
-
-                if char != '.' {
-                    // is a symbol!
-                    symbols.insert((x, y));
-
-                    if char == '*' {
-                        gears.insert((x, y));
-                    }
+                    num_start = Some(Point::new(x, y));
                 }
+            } else if let Some(start) = num_start.take() {
+                numbers.insert(start, parse_number(&num, start)?);
+                num = String::new();
             }
         }
 
-        if num.len() > 0 {
-            numbers.insert(
-                num_start.expect("No num start"),
-                num.parse().expect("Invalid number"),
-            );
-            num = String::new();
-            num_start = None;
+        if let Some(start) = num_start {
+            numbers.insert(start, parse_number(&num, start)?);
         }
     }
 
-    Map {
-        numbers,
-        symbols,
-        width,
-        height,
-        gears,
-    }
+    Ok(Map { grid, numbers })
+}
+
+fn parse_number(num: &str, start: Point) -> Result<usize, ParseError> {
+    num.parse()
+        .map_err(|_| ParseError::new(start.y + 1, start.x + 1, "a number that fits in a usize"))
 }
 
-pub fn solve_part_one<'a>(input: Map) -> String {
+pub fn solve_part_one(input: Map) -> Answer {
     let all_neighs = input.get_all_symbol_neighbours();
 
     input
         .numbers
         .iter()
-        .filter(|((x, y), num)| {
-            let start = x.clone();
-            let end = x + num.to_string().len();
-            for in_x in start..end {
-                if all_neighs.contains(&(in_x, *y)) {
+        .filter(|(start, num)| {
+            let end = start.x + num.to_string().len();
+            for x in start.x..end {
+                if all_neighs.contains(&Point::new(x, start.y)) {
                     return true;
                 }
             }
 
-            return false;
+            false
         })
         .map(|(_, num)| num)
         .sum::<usize>()
-        .to_string()
+        .into()
 }
 
-pub fn solve_part_two<'a>(input: Map) -> String {
-    let mut gear_to_num_map: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+pub fn solve_part_two(input: Map) -> Answer {
+    let mut gear_to_num_map: HashMap<Point, Vec<usize>> = HashMap::new();
 
-    let gear_positions: HashMap<(usize, usize), HashSet<(usize, usize)>> =
-        input.get_gear_neighbours();
+    let gear_positions: HashMap<Point, HashSet<Point>> = input.get_gear_neighbours();
 
-    for ((x, y), num) in input.numbers.iter() {
+    for (start, num) in input.numbers.iter() {
         let mut neighbouring_gears = vec![];
 
-        let start = x.clone();
-        let end = x + num.to_string().len();
-        for in_x in start..end {
-            if let Some(gear_positions) = gear_positions.get(&(in_x, *y)) {
+        let end = start.x + num.to_string().len();
+        for x in start.x..end {
+            if let Some(gear_positions) = gear_positions.get(&Point::new(x, start.y)) {
                 for gear_pos in gear_positions.iter() {
-                    if neighbouring_gears.contains(gear_pos) == false {
-                        neighbouring_gears.push(gear_pos.clone());
+                    if !neighbouring_gears.contains(gear_pos) {
+                        neighbouring_gears.push(*gear_pos);
 
                         if let Some(curr_nums) = gear_to_num_map.get_mut(gear_pos) {
-                            curr_nums.push(num.clone());
+                            curr_nums.push(*num);
                         } else {
-                            gear_to_num_map.insert(gear_pos.clone(), vec![num.clone()]);
+                            gear_to_num_map.insert(*gear_pos, vec![*num]);
                         }
                     }
                 }
@@ -187,5 +124,5 @@ pub fn solve_part_two<'a>(input: Map) -> String {
             a * b
         })
         .sum::<usize>()
-        .to_string()
+        .into()
 }
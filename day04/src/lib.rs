@@ -0,0 +1,81 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_one(input))
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_two(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError};
+
+    use super::Day04;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day04>(1, input), Ok(Answer::Integer(13)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day04>(2, input), Ok(Answer::Integer(30)));
+    }
+
+    #[test]
+    fn rejects_malformed_cards() {
+        let parse = |input| super::solution::parse(input).map(|cards| cards.len());
+
+        assert_eq!(parse("Card 1:  4 12 | 40 12 13"), Ok(1));
+        assert_eq!(
+            parse("Card 1: 4x 12 | 40 12 13"),
+            Err(ParseError::new(1, 10, "a digit"))
+        );
+        assert_eq!(
+            parse("Card 1:  4 12 | 40 12 1"),
+            Err(ParseError::new(1, 24, "a digit"))
+        );
+        assert_eq!(
+            parse("Crad 1:  4 12 | 40 12 13"),
+            Err(ParseError::new(1, 1, "`Card`"))
+        );
+        assert_eq!(
+            parse("Card 2:  4 12 | 40 12 13"),
+            Err(ParseError::new(1, 6, "card 1"))
+        );
+    }
+
+    #[test]
+    fn every_card_needs_both_separators() {
+        let parse = |input| super::solution::parse(input).map(|cards| cards.len());
+
+        assert_eq!(
+            parse("Card 1:  4 12 | 40 12 13\nCard 2 | 40 12 13:  4 12"),
+            Err(ParseError::new(2, 25, "`|`"))
+        );
+        assert_eq!(
+            parse("Card 1:  4 12 | 40 12 13\nCard 2:  4 12"),
+            Err(ParseError::new(2, 14, "`|`"))
+        );
+        assert_eq!(
+            parse("Card 1  4 12 | 40 12 13"),
+            Err(ParseError::new(1, 24, "`:`"))
+        );
+    }
+}
@@ -1,14 +0,0 @@
-#![feature(test)]
-#![feature(array_chunks)]
-#![feature(iter_array_chunks)]
-
-use generate_aoc_day::generate_day;
-
-mod solution;
-
-// Replace with desired year/day
-generate_day!(2023, 4);
-
-fn main() {
-    handle_day();
-}
@@ -1,58 +1,74 @@
 use std::collections::HashMap;
 
-const fn recode_char(b: &u8) -> u8 {
-    match b {
-        b' ' => 0,
-        b'1' => 1,
-        b'2' => 2,
-        b'3' => 3,
-        b'4' => 4,
-        b'5' => 5,
-        b'6' => 6,
-        b'7' => 7,
-        b'8' => 8,
-        b'9' => 9,
-        _ => 0,
-    }
+use common::{Answer, ParseError, Source};
+
+/// Decodes the numbers in `s`, each in a fixed-width column of a separating space and two
+/// right-aligned digits.
+fn recode_numbers(src: &Source, s: &str) -> Result<Vec<u8>, ParseError> {
+    let bytes = s.as_bytes();
+    // Everything before the byte being checked is ASCII, so it always starts a character.
+    let error = |at: usize, expected: &str| src.error(&s[at..], expected);
+
+    (0..bytes.len())
+        .step_by(3)
+        .map(|i| {
+            if bytes[i] != b' ' {
+                return Err(error(i, "` `"));
+            }
+            let tens = match bytes.get(i + 1) {
+                Some(b' ') => 0,
+                Some(b @ b'0'..=b'9') => b - b'0',
+                _ => return Err(error(i + 1, "a digit or ` `")),
+            };
+            let ones = match bytes.get(i + 2) {
+                Some(b @ b'0'..=b'9') => b - b'0',
+                _ => return Err(error(i + 2, "a digit")),
+            };
+            Ok(tens * 10 + ones)
+        })
+        .collect()
 }
 
-pub fn parse<'a>(input: &'a str) -> impl Iterator<Item = usize> + 'a {
-    let start = input.find(':').unwrap();
-    let middle = input.find('|').unwrap();
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let src = Source::new(input);
+
+    input
+        .lines()
+        .enumerate()
+        .map(move |(y, l)| {
+            let (card, numbers) = src.split_once(l, ":")?;
+            let (first, second) = src.split_once(numbers, "|")?;
+
+            // The cards are listed in order, so that part two can find the ones a card wins.
+            let card = src.strip_prefix(card, "Card")?.trim_start();
+            if src.number::<usize>(card)? != y + 1 {
+                return Err(src.error(card, format!("card {}", y + 1)));
+            }
 
-    input.lines().map(move |l| {
-        let first = l[start + 1..middle]
-            .as_bytes()
-            .iter()
-            .map(recode_char)
-            .array_chunks::<3>()
-            .map(|chunk| chunk[1] * 10 + chunk[2])
-            .collect::<Vec<u8>>();
+            let first = recode_numbers(&src, src.strip_suffix(first, " ")?)?;
 
-        l[middle + 1..]
-            .as_bytes()
-            .iter()
-            .map(recode_char)
-            .array_chunks::<3>()
-            .map(|chunk| chunk[1] * 10 + chunk[2])
-            .filter(|n| first.contains(n))
-            .count()
-    })
+            Ok(recode_numbers(&src, second)?
+                .into_iter()
+                .filter(|n| first.contains(n))
+                .count())
+        })
+        .collect()
 }
 
-pub fn solve_part_one<'a>(input: impl Iterator<Item = usize>) -> String {
+pub fn solve_part_one(input: Vec<usize>) -> Answer {
     input
+        .into_iter()
         .filter(|n| n > &0)
-        .map(|num| 1 << num - 1)
+        .map(|num| 1 << (num - 1))
         .sum::<u32>()
-        .to_string()
+        .into()
 }
 
-pub fn solve_part_two<'a>(input: impl Iterator<Item = usize>) -> String {
+pub fn solve_part_two(input: Vec<usize>) -> Answer {
     let mut duplication_map: HashMap<usize, u32> = HashMap::new();
     let mut count = 0;
 
-    for (index, card) in input.enumerate() {
+    for (index, card) in input.into_iter().enumerate() {
         let copies = duplication_map.get(&index).unwrap_or(&0) + 1;
         count += copies;
 
@@ -64,5 +80,5 @@ pub fn solve_part_two<'a>(input: impl Iterator<Item = usize>) -> String {
         }
     }
 
-    count.to_string()
+    count.into()
 }
@@ -0,0 +1,75 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed<'a> = solution::Instructions;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError, Solution, SolveError};
+
+    use super::Day05;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day05>(1, input), Ok(Answer::Integer(35)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day05>(2, input), Ok(Answer::Integer(46)));
+    }
+
+    #[test]
+    fn rejects_unpaired_seeds() {
+        assert_eq!(
+            Day05::parse("seeds: 79 14 55\n").map(|_| ()),
+            Err(ParseError::new(1, 16, "the length of the last seed range"))
+        );
+    }
+
+    #[test]
+    fn needs_maps_to_location() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n";
+        for part in 1..=2 {
+            assert_eq!(
+                solve_example::<Day05>(part, input),
+                Err(SolveError::UnsupportedInputShape(
+                    "no map leads from soil to location".to_string()
+                ))
+            );
+        }
+    }
+
+    // The maps are followed by their names, not the order they're listed in.
+    #[test]
+    fn maps_in_any_order() {
+        let (seeds, maps) = include_str!("../test_input.txt")
+            .split_once("\n\n")
+            .unwrap();
+        let mut maps = maps.trim_end().split("\n\n").collect::<Vec<&str>>();
+        maps.reverse();
+        let input = format!("{seeds}\n\n{}\n", maps.join("\n\n"));
+
+        assert_eq!(solve_example::<Day05>(1, &input), Ok(Answer::Integer(35)));
+        assert_eq!(solve_example::<Day05>(2, &input), Ok(Answer::Integer(46)));
+    }
+}
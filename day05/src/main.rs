@@ -1,12 +0,0 @@
-#![feature(test)]
-
-use generate_aoc_day::generate_day;
-
-mod solution;
-
-// Replace with desired year/day
-generate_day!(2023, 5);
-
-fn main() {
-    handle_day();
-}
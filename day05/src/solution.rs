@@ -1,8 +1,14 @@
 use std::collections::HashMap;
 
+use common::{
+    interval::{HyperRect, Interval, IntervalSet},
+    Answer, ParseError, SolveError, Source,
+};
+
 #[derive(Debug, Clone)]
 pub struct Instructions {
     seeds: Vec<i64>,
+    seed_ranges: Vec<Interval>,
     maps: Vec<Map>,
 }
 
@@ -16,160 +22,169 @@ pub struct Map {
 #[derive(Debug, Clone)]
 pub struct MapRange {
     diff: i64,
-    source_start: i64,
-    dest_start: i64,
-    length: i64,
+    source: Interval,
 }
 
-impl MapRange {
-    fn contains_source(&self, source: i64) -> bool {
-        source >= self.source_start && source < self.source_start + self.length
+impl Map {
+    /// Maps every value in `values` at once, splitting their intervals wherever they cross the
+    /// edge of a map range. Values outside every map range keep their value.
+    fn map_values(&self, values: IntervalSet<1>) -> IntervalSet<1> {
+        let mut mapped = IntervalSet::new();
+        let mut unmapped = values;
+
+        for map_range in &self.map_ranges {
+            let source = IntervalSet::from(HyperRect::new([map_range.source]));
+            mapped = mapped.union(&unmapped.intersect(&source).translate([map_range.diff]));
+            unmapped = unmapped.subtract(&source);
+        }
+
+        mapped.union(&unmapped)
     }
 }
 
-pub fn parse<'a>(input: &str) -> Instructions {
-    let (seed_line, input) = input.split_once("\n").unwrap();
-    let seeds = seed_line
-        .strip_prefix("seeds: ")
-        .unwrap()
-        .split(" ")
-        .map(|n| n.parse::<i64>().unwrap())
-        .collect::<Vec<i64>>();
+pub fn parse(input: &str) -> Result<Instructions, ParseError> {
+    let src = Source::new(input);
+
+    let (seed_line, input) = src.split_once(input, "\n")?;
+    let mut seeds = vec![];
+    let mut seed_ranges = vec![];
+    let mut numbers = src.strip_prefix(seed_line, "seeds: ")?.split(" ");
+    while let Some(start) = numbers.next() {
+        // Part two reads the seeds as pairs of a start and a length.
+        let length = numbers
+            .next()
+            .ok_or_else(|| src.error_after(seed_line, "the length of the last seed range"))?;
+        let (start, length) = (src.number(start)?, src.number(length)?);
+
+        seeds.extend([start, length]);
+        seed_ranges.push(Interval::new(start, start + length));
+    }
 
     let maps = input
         .split("\n\n")
         .filter(|l| !l.is_empty())
         .map(|l| l.trim())
         .map(|map_text| {
-            let (name, rest) = map_text.split_once("\n").expect("Failed to split map_text");
+            let (name, rest) = src.split_once(map_text, "\n")?;
 
-            let (source, dest) = name
-                .strip_suffix(" map:")
-                .unwrap()
-                .split_once("-to-")
-                .unwrap();
+            let (source, dest) = src.split_once(src.strip_suffix(name, " map:")?, "-to-")?;
 
             let map_ranges = rest
                 .lines()
                 .map(|numbers| {
-                    let (dest_start, rest) = numbers.split_once(" ").unwrap();
-                    let (source_start, length) = rest.split_once(" ").unwrap();
+                    let (dest_start, rest) = src.split_once(numbers, " ")?;
+                    let (source_start, length) = src.split_once(rest, " ")?;
 
-                    let s = source_start.parse().unwrap();
-                    let d = dest_start.parse().unwrap();
+                    let s = src.number(source_start)?;
+                    let d: i64 = src.number(dest_start)?;
                     let diff = d - s;
 
-                    MapRange {
+                    let length: i64 = src.number(length)?;
+
+                    Ok(MapRange {
                         diff,
-                        source_start: s,
-                        dest_start: d,
-                        length: length.parse().unwrap(),
-                    }
+                        source: Interval::new(s, s + length),
+                    })
                 })
-                .collect();
+                .collect::<Result<_, ParseError>>()?;
 
-            Map {
+            Ok(Map {
                 source_name: source.to_string(),
                 dest_name: dest.to_string(),
                 map_ranges,
-            }
+            })
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    Instructions { seeds, maps }
+    Ok(Instructions {
+        seeds,
+        seed_ranges,
+        maps,
+    })
 }
 
-pub fn solve_part_one<'a>(input: Instructions) -> String {
-    let mut seed_to_location_map: HashMap<i64, Vec<i64>> = HashMap::new();
-
-    for seed in input.seeds.iter() {
-        println!("Beginning with seed {seed}");
-
-        let mut source_name = "seed";
-        let mut curr_val = seed.clone();
-
-        seed_to_location_map.insert(seed.clone(), vec![]);
-
-        'search: while source_name != "location" {
-            println!("\tWorking with source {source_name}...");
-            for map in input.maps.iter() {
-                if map.source_name == source_name {
-                    let map_range = map
-                        .map_ranges
-                        .iter()
-                        .filter(|range| range.contains_source(curr_val))
-                        .collect::<Vec<&MapRange>>();
-
-                    if map_range.len() > 1 {
-                        // In case I was wrong :(
-                        panic!("Found a map with more than 1 matching range!");
-                    }
-
-                    if map_range.is_empty() {
-                        // Found no matching map range, keeping the same val.
-                        source_name = &map.dest_name;
-                        seed_to_location_map
-                            .get_mut(seed)
-                            .expect("SEED NOT FOUND IN MAP!")
-                            .push(curr_val);
-                        continue 'search;
-                    }
-
-                    curr_val = curr_val + map_range[0].diff;
-                    seed_to_location_map
-                        .get_mut(seed)
-                        .expect("SEED NOT FOUND IN MAP!")
-                        .push(curr_val);
-
-                    source_name = &map.dest_name;
-                    continue 'search;
-                }
-            }
-        }
+/// The maps to apply in order to turn a seed into a location, following their names.
+fn chain_to_location(maps: &[Map]) -> Result<Vec<&Map>, SolveError> {
+    let mut chain = vec![];
+    let mut source_name = "seed";
+
+    while source_name != "location" {
+        // Going through more maps than there are means the names lead in a circle.
+        let map = maps
+            .iter()
+            .find(|map| map.source_name == source_name)
+            .filter(|_| chain.len() < maps.len())
+            .ok_or_else(|| {
+                SolveError::UnsupportedInputShape(format!(
+                    "no map leads from {source_name} to location"
+                ))
+            })?;
+
+        chain.push(map);
+        source_name = &map.dest_name;
     }
 
-    let mut locations = seed_to_location_map
-        .into_iter()
-        .map(|(_, path)| path.last().unwrap().clone())
-        .collect::<Vec<i64>>();
-
-    locations.sort();
-
-    locations.first().unwrap().to_string()
+    Ok(chain)
 }
 
-pub fn solve_part_two<'a>(input: Instructions) -> String {
+pub fn solve_part_one(input: Instructions) -> Result<Answer, SolveError> {
+    let chain = chain_to_location(&input.maps)?;
     let mut seed_to_location_map: HashMap<i64, Vec<i64>> = HashMap::new();
 
-    let mut seeds = vec![];
-
-    for i in (0..input.seeds.len()).step_by(2) {
-        let seed = input.seeds[i];
-        let len = input.seeds[i + 1];
+    for seed in input.seeds.iter() {
+        let mut curr_val = *seed;
+
+        seed_to_location_map.insert(*seed, vec![]);
+
+        for map in chain.iter() {
+            let map_range = map
+                .map_ranges
+                .iter()
+                .filter(|range| range.source.contains(curr_val))
+                .collect::<Vec<&MapRange>>();
+
+            if map_range.len() > 1 {
+                return Err(SolveError::UnsupportedInputShape(format!(
+                    "more than one range of the {} map holds {curr_val}",
+                    map.source_name
+                )));
+            }
 
-        for s in (seed..seed + len) {
-            seeds.push(s);
+            // Found no matching map range, keeping the same val.
+            if let Some(map_range) = map_range.first() {
+                curr_val += map_range.diff;
+            }
+            seed_to_location_map
+                .get_mut(seed)
+                .expect("SEED NOT FOUND IN MAP!")
+                .push(curr_val);
         }
     }
 
-    let mut min_found = i64::MAX;
-
-    for seed in seeds.iter() {
-        let mut curr_val = seed.clone();
+    seed_to_location_map
+        .into_values()
+        .filter_map(|path| path.last().copied())
+        .min()
+        .map(Answer::from)
+        .ok_or_else(|| SolveError::Unsolvable("there are no seeds".to_string()))
+}
 
-        'map: for map in &input.maps {
-            for range in &map.map_ranges {
-                if range.contains_source(curr_val) {
-                    curr_val = curr_val + range.diff;
-                    continue 'map;
-                }
-            }
-        }
+pub fn solve_part_two(input: Instructions) -> Result<Answer, SolveError> {
+    // The seed ranges hold billions of seeds, so map whole ranges rather than single seeds.
+    let mut values = input
+        .seed_ranges
+        .iter()
+        .map(|&range| HyperRect::new([range]))
+        .collect::<IntervalSet<1>>();
 
-        if curr_val < min_found {
-            min_found = curr_val;
-        }
+    for map in chain_to_location(&input.maps)? {
+        values = map.map_values(values);
     }
 
-    min_found.to_string()
+    values
+        .rects()
+        .map(|rect| rect.sides[0].start)
+        .min()
+        .map(Answer::from)
+        .ok_or_else(|| SolveError::Unsolvable("every seed range is empty".to_string()))
 }
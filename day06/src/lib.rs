@@ -0,0 +1,73 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed<'a> = solution::Races;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError, SolveError};
+
+    use super::Day06;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day06>(1, input), Ok(Answer::Integer(288)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day06>(2, input), Ok(Answer::Integer(71503)));
+    }
+
+    #[test]
+    fn every_race_needs_a_distance() {
+        let parse = |input| super::solution::parse(input).map(|_| ());
+
+        assert_eq!(
+            parse("Time: 7 15 30\nDistance: 9"),
+            Err(ParseError::new(2, 12, "3 distances"))
+        );
+        assert_eq!(
+            parse("Time: 7\nDistance: 9 40"),
+            Err(ParseError::new(2, 13, "the end of the line"))
+        );
+    }
+
+    // Holding the button for half of a 2^33 ms race goes 2^64 mm.
+    #[test]
+    fn distance_too_large() {
+        let input = "Time: 8589934592\nDistance: 9";
+        assert!(matches!(
+            solve_example::<Day06>(1, input),
+            Err(SolveError::UnsupportedInputShape(_))
+        ));
+    }
+
+    #[test]
+    fn part_two_too_large() {
+        let input = "Time: 99999999999 99999999999\nDistance: 9 40";
+        assert!(matches!(
+            solve_example::<Day06>(2, input),
+            Err(SolveError::UnsupportedInputShape(_))
+        ));
+    }
+}
@@ -1,57 +1,81 @@
-pub fn parse<'a>(input: &'a str) -> &'a str {
-    input
+use common::{Answer, ParseError, SolveError, Source};
+
+pub struct Races {
+    times: Vec<u64>,
+    distances: Vec<u64>,
 }
 
-pub fn solve_part_one<'a>(input: &'a str) -> String {
-    let (time, distance) = input.split_once("\n").expect("No newline?");
-    let time_iter = time
-        .strip_prefix("Time:")
-        .unwrap()
-        .trim()
-        .split(" ")
-        .filter(|l| !l.is_empty())
-        .map(|n| n.parse::<u32>().unwrap());
-    let dist_iter = distance
-        .strip_prefix("Distance:")
-        .unwrap()
-        .trim()
-        .split(" ")
-        .filter(|l| !l.is_empty())
-        .map(|n| n.parse::<u32>().unwrap());
-
-    let input = std::iter::zip(time_iter, dist_iter);
-
-    input
-        .map(|(time, dist)| {
-            (0..=time)
-                .map(|t| (time - t) * t)
-                .filter(|d| d > &dist)
-                .count()
-        })
-        .fold(1, |acc, count| count * acc)
-        .to_string()
+pub fn parse<'a>(input: &'a str) -> Result<Races, ParseError> {
+    let src = Source::new(input);
+
+    let (time, distance) = src.split_once(input.trim_end(), "\n")?;
+    let numbers = |line: &'a str, prefix: &str| -> Result<Vec<&'a str>, ParseError> {
+        Ok(src
+            .strip_prefix(line, prefix)?
+            .split(" ")
+            .filter(|l| !l.is_empty())
+            .collect())
+    };
+    let times = numbers(time, "Time:")?;
+    let distances = numbers(distance, "Distance:")?;
+
+    // Every race needs a record distance to beat.
+    if let Some(extra) = distances.get(times.len()) {
+        return Err(src.error(extra, "the end of the line"));
+    }
+    if distances.len() < times.len() {
+        return Err(src.error_after(distance, format!("{} distances", times.len())));
+    }
+
+    let numbers = |numbers: Vec<&str>| {
+        numbers
+            .into_iter()
+            .map(|n| src.number::<u64>(n))
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    Ok(Races {
+        times: numbers(times)?,
+        distances: numbers(distances)?,
+    })
 }
 
-pub fn solve_part_two<'a>(input: &'a str) -> String {
-    let (time, distance) = input.split_once("\n").expect("No newline?");
-    let time = time
-        .strip_prefix("Time:")
-        .unwrap()
-        .trim()
-        .replace(" ", "")
-        .parse::<u64>()
-        .unwrap();
-    let dist = distance
-        .strip_prefix("Distance:")
-        .unwrap()
-        .trim()
-        .replace(" ", "")
-        .parse::<u64>()
-        .expect("Failed to parse dist");
-
-    (0..=time)
+fn ways_to_win(time: u64, dist: u64) -> Result<usize, SolveError> {
+    // Holding the button for half the race goes the furthest, so if that fits they all do.
+    let half = time / 2;
+    if (time - half).checked_mul(half).is_none() {
+        return Err(SolveError::UnsupportedInputShape(format!(
+            "a {time} ms race goes further than 64 bits can count"
+        )));
+    }
+
+    Ok((0..=time)
         .map(|t| (time - t) * t)
         .filter(|d| d > &dist)
-        .count()
-        .to_string()
+        .count())
+}
+
+/// Reads the numbers as one, ignoring the spaces between them.
+fn concat(numbers: &[u64]) -> Result<u64, SolveError> {
+    numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<String>()
+        .parse()
+        .map_err(|_| {
+            SolveError::UnsupportedInputShape(format!(
+                "{numbers:?} read as one number doesn't fit in 64 bits"
+            ))
+        })
+}
+
+pub fn solve_part_one(input: Races) -> Result<Answer, SolveError> {
+    Ok(std::iter::zip(input.times, input.distances)
+        .map(|(time, dist)| ways_to_win(time, dist))
+        .product::<Result<usize, SolveError>>()?
+        .into())
+}
+
+pub fn solve_part_two(input: Races) -> Result<Answer, SolveError> {
+    Ok(ways_to_win(concat(&input.times)?, concat(&input.distances)?)?.into())
 }
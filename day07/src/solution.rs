@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use common::{Answer, ParseError, Source};
+
 #[derive(PartialEq, PartialOrd)]
 pub struct Hand {
     hand: Vec<char>,
@@ -67,7 +69,7 @@ impl Hand {
             acc
         });
 
-        let num_jokers = map.get(&'J').unwrap_or(&0).clone();
+        let num_jokers = *map.get(&'J').unwrap_or(&0);
 
         let different = map.len() - if num_jokers > 0 { 1 } else { 0 };
         let count = map
@@ -130,19 +132,34 @@ fn char_val_2(c: &char) -> u32 {
     }
 }
 
-pub fn parse<'a>(input: &'a str) -> impl Iterator<Item = Hand> + 'a {
-    input.lines().map(|l| {
-        let (hand, bid) = l.split_once(" ").expect("Failed to split");
+pub fn parse(input: &str) -> Result<Vec<Hand>, ParseError> {
+    let src = Source::new(input);
 
-        Hand {
-            hand: hand.chars().collect(),
-            bid: bid.parse().expect("Failed to parse bid"),
-        }
-    })
+    input
+        .lines()
+        .map(|l| {
+            let (hand, bid) = src.split_once(l, " ")?;
+
+            if let Some((i, _)) = hand
+                .char_indices()
+                .find(|(_, c)| !"23456789TJQKA".contains(*c))
+            {
+                return Err(src.error(&hand[i..], "a card (`2`-`9`, `T`, `J`, `Q`, `K` or `A`)"));
+            }
+            if hand.chars().count() != 5 {
+                return Err(src.error(hand, "a hand of five cards"));
+            }
+
+            Ok(Hand {
+                hand: hand.chars().collect(),
+                bid: src.number(bid)?,
+            })
+        })
+        .collect()
 }
 
-pub fn solve_part_one<'a>(input: impl Iterator<Item = Hand>) -> String {
-    let mut inp = input.collect::<Vec<Hand>>();
+pub fn solve_part_one(input: Vec<Hand>) -> Answer {
+    let mut inp = input;
     inp.sort_by(|h1, h2| {
         let t1 = h1.get_type();
         let t2 = h2.get_type();
@@ -160,18 +177,18 @@ pub fn solve_part_one<'a>(input: impl Iterator<Item = Hand>) -> String {
             }
         }
 
-        return t1.partial_cmp(&t2).unwrap();
+        t1.partial_cmp(&t2).unwrap()
     });
 
     inp.iter()
         .enumerate()
         .map(|(i, hand)| (i + 1) * hand.bid as usize)
         .sum::<usize>()
-        .to_string()
+        .into()
 }
 
-pub fn solve_part_two<'a>(input: impl Iterator<Item = Hand>) -> String {
-    let mut inp = input.collect::<Vec<Hand>>();
+pub fn solve_part_two(input: Vec<Hand>) -> Answer {
+    let mut inp = input;
     inp.sort_by(|h1, h2| {
         let t1 = h1.get_type_2();
         let t2 = h2.get_type_2();
@@ -189,12 +206,12 @@ pub fn solve_part_two<'a>(input: impl Iterator<Item = Hand>) -> String {
             }
         }
 
-        return t1.partial_cmp(&t2).unwrap();
+        t1.partial_cmp(&t2).unwrap()
     });
 
     inp.iter()
         .enumerate()
         .map(|(i, hand)| (i + 1) * hand.bid as usize)
         .sum::<usize>()
-        .to_string()
+        .into()
 }
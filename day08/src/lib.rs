@@ -0,0 +1,82 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed<'a> = solution::Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError, Solution, SolveError};
+
+    use super::Day08;
+
+    #[test]
+    fn part_one_example_1() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day08>(1, input), Ok(Answer::Integer(2)));
+    }
+
+    #[test]
+    fn part_one_example_2() {
+        let input = include_str!("../test_input2.txt");
+        assert_eq!(solve_example::<Day08>(1, input), Ok(Answer::Integer(6)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input3.txt");
+        assert_eq!(solve_example::<Day08>(2, input), Ok(Answer::Integer(6)));
+    }
+
+    // The first ghost is on a `Z` node every other step from step 2, the second every third
+    // step from step 1, so multiplying the first steps would give 2.
+    #[test]
+    fn part_two_offset_cycles() {
+        let input = include_str!("../test_input4.txt");
+        assert_eq!(solve_example::<Day08>(2, input), Ok(Answer::Integer(4)));
+    }
+
+    #[test]
+    fn part_two_never_together() {
+        let input = include_str!("../test_input5.txt");
+        assert!(matches!(
+            solve_example::<Day08>(2, input),
+            Err(SolveError::Unsolvable(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_instructions() {
+        assert_eq!(
+            Day08::parse("\n\nAAA = (BBB, BBB)").map(|_| ()),
+            Err(ParseError::new(1, 1, "`L` or `R`"))
+        );
+    }
+
+    #[test]
+    fn part_one_going_in_circles() {
+        let input = "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(
+            solve_example::<Day08>(1, input),
+            Err(SolveError::Unsolvable(
+                "`ZZZ` can't be reached from `AAA`".to_string()
+            ))
+        );
+    }
+}
@@ -1,90 +1,132 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use common::{
+    cycle::{self, Periodic},
+    Answer, Direction, ParseError, SolveError, Source,
+};
 
 pub struct Map {
-    instructions: Vec<Dir>,
-    map: HashMap<(String, Dir), String>,
+    instructions: Vec<Direction>,
+    map: HashMap<(String, Direction), String>,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub enum Dir {
-    Left,
-    Right,
-}
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    let src = Source::new(input);
 
-pub fn parse<'a>(input: &str) -> Map {
-    let (lr_ins, map) = input.split_once("\n\n").unwrap();
+    let (lr_ins, map) = src.split_once(input, "\n\n")?;
+    if lr_ins.is_empty() {
+        return Err(src.error(lr_ins, "`L` or `R`"));
+    }
 
     let instructions = lr_ins
-        .chars()
-        .map(|c| match c {
-            'L' => Dir::Left,
-            'R' => Dir::Right,
-            _ => panic!("Invalid instruction {c}"),
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' | 'R' => Ok(Direction::from_letter(c).unwrap()),
+            _ => Err(src.error(&lr_ins[i..], "`L` or `R`")),
         })
-        .collect::<Vec<Dir>>();
+        .collect::<Result<Vec<Direction>, ParseError>>()?;
 
     let mut map_map = HashMap::new();
     for l in map.lines() {
-        let (start, end) = l.split_once(" = ").unwrap();
-        let (left, right) = end
-            .strip_prefix("(")
-            .unwrap()
-            .strip_suffix(")")
-            .unwrap()
-            .split_once(", ")
-            .unwrap();
-
-        map_map.insert((start.to_string(), Dir::Left), left.to_string());
-        map_map.insert((start.to_string(), Dir::Right), right.to_string());
+        let (start, end) = src.split_once(l, " = ")?;
+        let (left, right) =
+            src.split_once(src.strip_suffix(src.strip_prefix(end, "(")?, ")")?, ", ")?;
+
+        map_map.insert((start.to_string(), Direction::Left), left.to_string());
+        map_map.insert((start.to_string(), Direction::Right), right.to_string());
     }
 
-    Map {
+    Ok(Map {
         instructions,
         map: map_map,
-    }
+    })
 }
 
-fn get_steps_for_start(start: String, input: &Map, part1: bool) -> i64 {
+fn get_steps_for_start(start: String, input: &Map) -> Result<usize, SolveError> {
     let mut curr = start.clone();
     let mut ins_index = 0;
     let mut steps = 0;
 
-    while (part1 && curr != "ZZZ") || (!part1 && !curr.ends_with("Z")) {
-        let ins = input.instructions[ins_index].clone();
+    // Once every node has been left at every point in the instructions, it's going in circles.
+    let max_steps = input.map.len() / 2 * input.instructions.len();
+
+    while curr != "ZZZ" {
+        if steps > max_steps {
+            return Err(SolveError::Unsolvable(format!(
+                "`ZZZ` can't be reached from `{start}`"
+            )));
+        }
+
+        let ins = input.instructions[ins_index];
         ins_index = (ins_index + 1) % input.instructions.len();
 
-        let dir = input
-            .map
-            .get(&(curr, ins))
-            .expect("Couldn't find step in map :(");
+        let Some(dir) = input.map.get(&(curr.clone(), ins)) else {
+            return Err(SolveError::UnsupportedInputShape(format!(
+                "there is no node `{curr}` to step from"
+            )));
+        };
 
         curr = dir.clone();
         steps += 1;
     }
 
-    steps
+    Ok(steps)
+}
+
+/// Whether the ghost starting from `start` is on a node ending in `Z` after each step, using the
+/// cycle its node and position in the instructions fall into.
+fn follow_ghost(start: &str, input: &Map) -> Result<Periodic, SolveError> {
+    // A state is `None` once the ghost has reached a node with nowhere to go.
+    let (cycle, states) = cycle::find_cycle_hashed(Some((start, 0)), |state| {
+        let (node, ins_index) = (*state)?;
+        let ins = input.instructions[ins_index];
+        let next = input.map.get(&(node.to_string(), ins))?;
+        Some((next.as_str(), (ins_index + 1) % input.instructions.len()))
+    });
+
+    if let Some(pair) = states.windows(2).find(|pair| pair[1].is_none()) {
+        let (node, _) = pair[0].unwrap();
+        return Err(SolveError::UnsupportedInputShape(format!(
+            "there is no node `{node}` to step from"
+        )));
+    }
+
+    Ok(Periodic {
+        cycle,
+        holds: states
+            .iter()
+            .map(|state| state.is_some_and(|(node, _)| node.ends_with('Z')))
+            .collect(),
+    })
 }
 
-pub fn solve_part_one<'a>(input: Map) -> String {
-    let steps = get_steps_for_start(String::from("AAA"), &input, true);
+pub fn solve_part_one(input: Map) -> Result<Answer, SolveError> {
+    let steps = get_steps_for_start(String::from("AAA"), &input)?;
 
-    steps.to_string()
+    Ok(steps.into())
 }
 
-pub fn solve_part_two<'a>(input: Map) -> String {
-    let starts = input
+/// Follows every ghost until it's in a cycle, then finds the first step at which they're all on
+/// a `Z` node at once.
+pub fn solve_part_two(input: Map) -> Result<Answer, SolveError> {
+    let ghosts = input
         .map
         .keys()
         .map(|(s, _)| s)
-        .filter(|s| s.ends_with("A"))
-        .cloned()
-        .collect::<Vec<String>>();
-    let mut steps = 1;
-
-    for s in starts {
-        let curr_steps = get_steps_for_start(s, &input, false);
-        steps = num::integer::lcm(steps, curr_steps);
+        .filter(|s| s.ends_with('A'))
+        .collect::<HashSet<&String>>()
+        .into_iter()
+        .map(|start| follow_ghost(start, &input))
+        .collect::<Result<Vec<Periodic>, SolveError>>()?;
+    if ghosts.is_empty() {
+        return Err(SolveError::UnsupportedInputShape(
+            "no node ends in `A`".to_string(),
+        ));
     }
 
-    steps.to_string()
+    cycle::first_common_step(&ghosts)
+        .map(Answer::from)
+        .ok_or_else(|| {
+            SolveError::Unsolvable("the ghosts are never all on a `Z` node at once".to_string())
+        })
 }
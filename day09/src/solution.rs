@@ -1,10 +1,15 @@
-pub fn parse<'a>(input: &'a str) -> impl Iterator<Item = Vec<i64>> + 'a {
+use common::{Answer, ParseError, Source};
+
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    let src = Source::new(input);
+
     input
         .lines()
-        .map(|l| l.split(" ").map(|n| n.parse().unwrap()).collect())
+        .map(|l| l.split(" ").map(|n| src.number(n)).collect())
+        .collect()
 }
 
-pub fn solve_part_one<'a>(input: impl Iterator<Item = Vec<i64>>) -> String {
+pub fn solve_part_one(input: Vec<Vec<i64>>) -> Answer {
     let mut sum = 0;
 
     for row in input {
@@ -44,10 +49,10 @@ pub fn solve_part_one<'a>(input: impl Iterator<Item = Vec<i64>>) -> String {
 
         sum += lines.first().unwrap().last().unwrap();
     }
-    sum.to_string()
+    sum.into()
 }
 
-pub fn solve_part_two<'a>(input: impl Iterator<Item = Vec<i64>>) -> String {
+pub fn solve_part_two(input: Vec<Vec<i64>>) -> Answer {
     let mut sum = 0;
 
     for row in input {
@@ -87,5 +92,5 @@ pub fn solve_part_two<'a>(input: impl Iterator<Item = Vec<i64>>) -> String {
 
         sum += lines.first().unwrap().first().unwrap();
     }
-    sum.to_string()
+    sum.into()
 }
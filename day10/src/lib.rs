@@ -0,0 +1,93 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed<'a> = common::Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+
+    fn svg(input: Self::Parsed<'_>, part: u8) -> Option<Result<String, SolveError>> {
+        Some(solution::svg(&input, part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError};
+
+    use super::{solution, Day10};
+
+    #[test]
+    fn part_one_example_1() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day10>(1, input), Ok(Answer::Integer(8)));
+    }
+
+    #[test]
+    fn part_one_example_2() {
+        let input = include_str!("../test_input2.txt");
+        assert_eq!(solve_example::<Day10>(1, input), Ok(Answer::Integer(4)));
+    }
+
+    #[test]
+    fn part_two_example_1() {
+        let input = include_str!("../test_input3.txt");
+        assert_eq!(solve_example::<Day10>(2, input), Ok(Answer::Integer(4)));
+    }
+
+    #[test]
+    fn part_two_example_2() {
+        let input = include_str!("../test_input4.txt");
+        assert_eq!(solve_example::<Day10>(2, input), Ok(Answer::Integer(8)));
+    }
+
+    #[test]
+    fn draws_the_loop() {
+        let map = solution::parse(include_str!("../test_input3.txt")).unwrap();
+        let svg = solution::svg(&map, 2).unwrap();
+
+        assert!(svg.contains(
+            r#"<polygon points="1,1 9,1 9,7 6,7 6,5 8,5 8,2 2,2 2,5 4,5 4,7 1,7" fill="none""#
+        ));
+        // The four tiles inside the loop, on the seventh row.
+        let shaded = svg
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.contains(r##"fill="#8fbc8f""##))
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            shaded,
+            [
+                r##"<rect x="1.5" y="5.5" width="1" height="1" fill="#8fbc8f"/>"##,
+                r##"<rect x="2.5" y="5.5" width="1" height="1" fill="#8fbc8f"/>"##,
+                r##"<rect x="6.5" y="5.5" width="1" height="1" fill="#8fbc8f"/>"##,
+                r##"<rect x="7.5" y="5.5" width="1" height="1" fill="#8fbc8f"/>"##,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_a_second_start() {
+        assert_eq!(
+            solution::parse("S-7.\n|.|.\nL-S.\n....").map(|map| map.width()),
+            Err(ParseError::new(
+                3,
+                3,
+                "a pipe instead of a second start tile `S`"
+            ))
+        );
+    }
+}
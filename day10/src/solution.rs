@@ -1,260 +1,192 @@
-use std::{
-    collections::{HashMap, HashSet},
-    ops::Div,
+use common::{
+    geometry::polygon::{self, Vertex},
+    svg::Svg,
+    Answer, Direction, Grid, ParseError, Point, SolveError, Source,
 };
 
-pub fn parse<'a>(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|l| l.chars().collect()).collect()
-}
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    let src = Source::new(input);
+    let map = src.char_grid(input, "|-LJ7F.S")?;
 
-fn get_start_pos(map: &Vec<Vec<char>>) -> (usize, usize) {
-    for (y, row) in map.iter().enumerate() {
-        for (x, tile) in row.iter().enumerate() {
-            if tile == &'S' {
-                return (x, y);
-            }
-        }
+    if let Some((second, _)) = input.match_indices('S').nth(1) {
+        return Err(src.error(
+            &input[second..],
+            "a pipe instead of a second start tile `S`",
+        ));
     }
 
-    panic!("ASD")
+    Ok(map)
 }
 
-#[derive(Debug, Clone)]
-enum Dir {
-    Up,
-    Right,
-    Down,
-    Left,
+fn get_start_pos(map: &Grid<char>) -> Result<Point, SolveError> {
+    map.position(|&tile| tile == 'S')
+        .ok_or_else(|| SolveError::UnsupportedInputShape("no start tile `S`".to_string()))
 }
 
-fn get_next_dir_at_start(map: &Vec<Vec<char>>, start_x: &usize, start_y: &usize) -> Dir {
-    if start_y > &0 {
-        match map[start_y - 1][*start_x] {
-            '|' => return Dir::Up,
-            '7' => return Dir::Up,
-            'F' => return Dir::Up,
-            _ => {}
-        }
+/// The tile a step from `p` in `dir`, if that's on the map.
+fn tile_at(map: &Grid<char>, p: Point, dir: Direction) -> Option<char> {
+    map.step(p, dir).map(|next| map[next])
+}
+
+/// Follows the loop a step from `p` in `dir`.
+fn follow(map: &Grid<char>, p: Point, dir: Direction) -> Result<Point, SolveError> {
+    map.step(p, dir)
+        .ok_or_else(|| SolveError::UnsupportedInputShape(format!("the loop leaves the map at {p}")))
+}
+
+/// The direction the pipe `tile` leads on in, having entered it from `came_from`.
+fn next_dir(tile: char, came_from: Direction) -> Result<Direction, SolveError> {
+    match (tile, came_from) {
+        ('|', Direction::Up) => Ok(Direction::Down),
+        ('|', Direction::Down) => Ok(Direction::Up),
+        ('-', Direction::Left) => Ok(Direction::Right),
+        ('-', Direction::Right) => Ok(Direction::Left),
+        ('L', Direction::Up) => Ok(Direction::Right),
+        ('L', Direction::Right) => Ok(Direction::Up),
+        ('J', Direction::Up) => Ok(Direction::Left),
+        ('J', Direction::Left) => Ok(Direction::Up),
+        ('7', Direction::Left) => Ok(Direction::Down),
+        ('7', Direction::Down) => Ok(Direction::Left),
+        ('F', Direction::Right) => Ok(Direction::Down),
+        ('F', Direction::Down) => Ok(Direction::Right),
+        (c, d) => Err(SolveError::UnsupportedInputShape(format!(
+            "'{c}' entered from {d} doesn't continue the loop"
+        ))),
     }
+}
 
-    if let Some(c) = map[*start_y].get(start_x + 1) {
-        match c {
-            '-' => return Dir::Right,
-            'J' => return Dir::Right,
-            '7' => return Dir::Right,
-            _ => {}
+/// Like [`next_dir`] for the tile at `p`, saying where the loop breaks if it does.
+fn next_dir_at(map: &Grid<char>, p: Point, came_from: Direction) -> Result<Direction, SolveError> {
+    next_dir(map[p], came_from).map_err(|err| match err {
+        SolveError::UnsupportedInputShape(reason) => {
+            SolveError::UnsupportedInputShape(format!("the loop breaks at {p}: {reason}"))
         }
+        err => err,
+    })
+}
+
+fn get_next_dir_at_start(map: &Grid<char>, start: Point) -> Result<Direction, SolveError> {
+    if let Some('|' | '7' | 'F') = tile_at(map, start, Direction::Up) {
+        return Ok(Direction::Up);
     }
 
-    if let Some(row) = map.get(start_y + 1) {
-        match row[*start_x] {
-            '|' => return Dir::Down,
-            'L' => return Dir::Down,
-            'J' => return Dir::Down,
-            _ => {}
-        }
+    if let Some('-' | 'J' | '7') = tile_at(map, start, Direction::Right) {
+        return Ok(Direction::Right);
     }
 
-    if start_x > &0 {
-        match map[*start_y][start_x - 1] {
-            '-' => return Dir::Left,
-            'L' => return Dir::Left,
-            'F' => return Dir::Left,
-            _ => {}
-        }
+    if let Some('|' | 'L' | 'J') = tile_at(map, start, Direction::Down) {
+        return Ok(Direction::Down);
     }
 
-    panic!("no available dir from start! Expected 2");
-}
+    if let Some('-' | 'L' | 'F') = tile_at(map, start, Direction::Left) {
+        return Ok(Direction::Left);
+    }
 
-pub fn solve_part_one<'a>(input: Vec<Vec<char>>) -> String {
-    let (start_x, start_y) = get_start_pos(&input);
+    Err(SolveError::UnsupportedInputShape(
+        "no pipe connects to the start tile".to_string(),
+    ))
+}
 
-    let mut curr_x = start_x;
-    let mut curr_y = start_y;
+pub fn solve_part_one(input: Grid<char>) -> Result<Answer, SolveError> {
+    let start = get_start_pos(&input)?;
+    let mut curr = start;
 
-    let mut came_from = Dir::Up; // Randomly selected;
-    let mut next_dir = get_next_dir_at_start(&input, &start_x, &start_y);
+    let mut came_from;
+    let mut next_dir = get_next_dir_at_start(&input, start)?;
 
-    let mut steps = 0;
+    let mut steps: usize = 0;
 
     loop {
-        match next_dir {
-            Dir::Up => {
-                came_from = Dir::Down;
-                curr_y -= 1;
-            }
-            Dir::Right => {
-                came_from = Dir::Left;
-                curr_x += 1;
-            }
-            Dir::Down => {
-                came_from = Dir::Up;
-                curr_y += 1;
-            }
-            Dir::Left => {
-                came_from = Dir::Right;
-                curr_x -= 1;
-            }
-        }
+        came_from = next_dir.opposite();
+        curr = follow(&input, curr, next_dir)?;
 
-        match (input[curr_y][curr_x], came_from) {
-            ('|', Dir::Up) => next_dir = Dir::Down,
-            ('|', Dir::Down) => next_dir = Dir::Up,
-            ('-', Dir::Left) => next_dir = Dir::Right,
-            ('-', Dir::Right) => next_dir = Dir::Left,
-            ('L', Dir::Up) => next_dir = Dir::Right,
-            ('L', Dir::Right) => next_dir = Dir::Up,
-            ('J', Dir::Up) => next_dir = Dir::Left,
-            ('J', Dir::Left) => next_dir = Dir::Up,
-            ('7', Dir::Left) => next_dir = Dir::Down,
-            ('7', Dir::Down) => next_dir = Dir::Left,
-            ('F', Dir::Right) => next_dir = Dir::Down,
-            ('F', Dir::Down) => next_dir = Dir::Right,
-            ('S', _) => break,
-            ('.', _) => panic!("Got to ground tile!"),
-
-            (c, d) => {
-                panic!("Invalid combination '{d:?}' '{c}'")
-            }
+        if input[curr] == 'S' {
+            break;
         }
+        next_dir = next_dir_at(&input, curr, came_from)?;
 
         steps += 1;
     }
 
-    let half = steps as f64 / 2.0;
-    half.ceil().to_string()
+    Ok(steps.div_ceil(2).into())
 }
 
-fn get_start_char(map: &Vec<Vec<char>>, start_x: &usize, start_y: &usize) -> char {
-    let up = if start_y > &0 {
-        match map[start_y - 1][*start_x] {
-            '|' => true,
-            '7' => true,
-            'F' => true,
-            _ => false,
-        }
-    } else {
-        false
-    };
-
-    let right = if let Some(c) = map[*start_y].get(start_x + 1) {
-        match c {
-            '-' => true,
-            'J' => true,
-            '7' => true,
-            _ => false,
-        }
-    } else {
-        false
-    };
-
-    let down = if let Some(row) = map.get(start_y + 1) {
-        match row[*start_x] {
-            '|' => true,
-            'L' => true,
-            'J' => true,
-            _ => false,
-        }
-    } else {
-        false
-    };
-
-    let left = if start_x > &0 {
-        match map[*start_y][start_x - 1] {
-            '-' => true,
-            'L' => true,
-            'F' => true,
-            _ => false,
-        }
-    } else {
-        false
-    };
+fn get_start_char(map: &Grid<char>, start: Point) -> Result<char, SolveError> {
+    let up = matches!(tile_at(map, start, Direction::Up), Some('|' | '7' | 'F'));
+    let right = matches!(tile_at(map, start, Direction::Right), Some('-' | 'J' | '7'));
+    let down = matches!(tile_at(map, start, Direction::Down), Some('|' | 'L' | 'J'));
+    let left = matches!(tile_at(map, start, Direction::Left), Some('-' | 'L' | 'F'));
 
     match (up, right, down, left) {
-        (true, true, false, false) => 'L',
-        (true, false, true, false) => '|',
-        (true, false, false, true) => 'J',
-        (false, true, true, false) => 'F',
-        (false, true, false, true) => '-',
-        (false, false, true, true) => '7',
-        _ => panic!("Invalid start tile!"),
+        (true, true, false, false) => Ok('L'),
+        (true, false, true, false) => Ok('|'),
+        (true, false, false, true) => Ok('J'),
+        (false, true, true, false) => Ok('F'),
+        (false, true, false, true) => Ok('-'),
+        (false, false, true, true) => Ok('7'),
+        _ => Err(SolveError::UnsupportedInputShape(
+            "the start tile doesn't connect to exactly two pipes".to_string(),
+        )),
     }
 }
 
-pub fn solve_part_two<'a>(input: Vec<Vec<char>>) -> String {
-    let mut map = input;
+/// The tiles where the main loop turns, in order, as the vertices of a polygon through their
+/// centres.
+fn loop_corners(input: &Grid<char>) -> Result<Vec<Vertex>, SolveError> {
+    let mut map = input.clone();
 
-    let (start_x, start_y) = get_start_pos(&map);
-    map[start_y][start_x] = get_start_char(&map, &start_x, &start_y);
+    let start = get_start_pos(&map)?;
+    map[start] = get_start_char(&map, start)?;
 
-    let mut part_of_loop: HashSet<(usize, usize)> = HashSet::new();
+    let mut pipe_loop: Vec<Vertex> = vec![];
 
-    let mut curr_x = start_x;
-    let mut curr_y = start_y;
-    let mut came_from = Dir::Up; // Randomly selected;
-    let mut next_dir = get_next_dir_at_start(&map, &start_x, &start_y);
+    let mut curr = start;
+    let mut came_from;
+    let mut next_dir = get_next_dir_at_start(&map, start)?;
     loop {
-        part_of_loop.insert((curr_x, curr_y));
-        match next_dir {
-            Dir::Up => {
-                came_from = Dir::Down;
-                curr_y -= 1;
-            }
-            Dir::Right => {
-                came_from = Dir::Left;
-                curr_x += 1;
-            }
-            Dir::Down => {
-                came_from = Dir::Up;
-                curr_y += 1;
-            }
-            Dir::Left => {
-                came_from = Dir::Right;
-                curr_x -= 1;
-            }
+        if !matches!(map[curr], '|' | '-') {
+            pipe_loop.push((curr.x as i64, curr.y as i64));
         }
+        came_from = next_dir.opposite();
+        curr = follow(&map, curr, next_dir)?;
 
-        if curr_x == start_x && curr_y == start_y {
+        if curr == start {
             break;
         }
 
-        match (map[curr_y][curr_x], came_from) {
-            ('|', Dir::Up) => next_dir = Dir::Down,
-            ('|', Dir::Down) => next_dir = Dir::Up,
-            ('-', Dir::Left) => next_dir = Dir::Right,
-            ('-', Dir::Right) => next_dir = Dir::Left,
-            ('L', Dir::Up) => next_dir = Dir::Right,
-            ('L', Dir::Right) => next_dir = Dir::Up,
-            ('J', Dir::Up) => next_dir = Dir::Left,
-            ('J', Dir::Left) => next_dir = Dir::Up,
-            ('7', Dir::Left) => next_dir = Dir::Down,
-            ('7', Dir::Down) => next_dir = Dir::Left,
-            ('F', Dir::Right) => next_dir = Dir::Down,
-            ('F', Dir::Down) => next_dir = Dir::Right,
-            ('S', _) => panic!("S tile should've been removed!"),
-            ('.', _) => panic!("Got to ground tile!"),
-
-            (c, d) => {
-                panic!("Invalid combination '{d:?}' '{c}'")
-            }
-        };
+        next_dir = next_dir_at(&map, curr, came_from)?;
     }
 
-    let vertical_chars = vec!['|', 'J', 'L'];
-    let mut count = 0;
-    for (y, row) in map.into_iter().enumerate() {
-        let mut inside = false;
-        for (x, c) in row.into_iter().enumerate() {
-            if part_of_loop.contains(&(x, y)) {
-                if vertical_chars.contains(&c) {
-                    inside = !inside;
-                }
-            } else if inside {
-                count += 1;
-            }
+    Ok(pipe_loop)
+}
+
+pub fn solve_part_two(input: Grid<char>) -> Result<Answer, SolveError> {
+    // The enclosed tiles are the lattice points strictly inside the loop.
+    Ok(polygon::interior_points(&loop_corners(&input)?).into())
+}
+
+/// Draws the main loop through the centres of its tiles, with the tiles it encloses shaded.
+pub fn svg(map: &Grid<char>, _part: u8) -> Result<String, SolveError> {
+    let corners = loop_corners(map)?;
+
+    let mut svg = Svg::new();
+    svg.rect(
+        (-0.5, -0.5),
+        (map.width() as f64, map.height() as f64),
+        "#f4f4f4",
+    );
+    for p in map.points() {
+        let tile = (p.x as i64, p.y as i64);
+        if polygon::contains(&corners, tile) && !polygon::on_boundary(&corners, tile) {
+            svg.rect((p.x as f64 - 0.5, p.y as f64 - 0.5), (1.0, 1.0), "#8fbc8f");
         }
     }
 
-    count.to_string()
+    let points = corners
+        .iter()
+        .map(|&(x, y)| (x as f64, y as f64))
+        .collect::<Vec<(f64, f64)>>();
+    svg.polygon(&points, "none", "black");
+
+    Ok(svg.to_string())
 }
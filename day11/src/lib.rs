@@ -0,0 +1,42 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = common::Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_one(input))
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_two(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer};
+
+    use super::{solution, Day11};
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_file.txt");
+        assert_eq!(solve_example::<Day11>(1, input), Ok(Answer::Integer(374)));
+    }
+
+    // Part two's example is given for expansions of 10 and 100 rather than a million.
+    #[test]
+    fn expansion_examples() {
+        let input = solution::parse(include_str!("../test_file.txt")).unwrap();
+        assert_eq!(solution::sum_of_distances(&input, 10), 1030);
+        assert_eq!(solution::sum_of_distances(&input, 100), 8410);
+    }
+}
@@ -0,0 +1,58 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed<'a> = Vec<solution::Line>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_two(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, SolveError};
+
+    use super::Day12;
+
+    #[test]
+    fn part_one_example_1() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day12>(1, input), Ok(Answer::Integer(1)));
+    }
+
+    #[test]
+    fn part_one_example_2() {
+        let input = include_str!("../test_input2.txt");
+        assert_eq!(solve_example::<Day12>(1, input), Ok(Answer::Integer(21)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input2.txt");
+        assert_eq!(
+            solve_example::<Day12>(2, input),
+            Ok(Answer::Integer(525152))
+        );
+    }
+
+    #[test]
+    fn part_one_too_many_unknowns() {
+        let input = format!("{} 1", "?".repeat(64));
+        assert!(matches!(
+            solve_example::<Day12>(1, &input),
+            Err(SolveError::UnsupportedInputShape(_))
+        ));
+    }
+}
@@ -1,4 +1,6 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+
+use common::{Answer, ParseError, SolveError, Source};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum C {
@@ -13,48 +15,41 @@ pub struct Line {
     operationals: Vec<usize>,
 }
 
-pub fn parse<'a>(input: &'a str) -> &'a str {
-    input
-}
+pub fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    let src = Source::new(input);
 
-fn parse_part_one<'a>(input: &'a str) -> Vec<Line> {
     input
         .lines()
         .map(|l| {
-            let (map, info) = l.split_once(" ").unwrap();
+            let (map, info) = src.split_once(l, " ")?;
             let springs = map
-                .chars()
-                .map(|c| match c {
-                    '?' => C::Unknown,
-                    '#' => C::Operational,
-                    '.' => C::Broken,
-                    _ => panic!("Invalid input char {c}"),
+                .char_indices()
+                .map(|(i, c)| match c {
+                    '?' => Ok(C::Unknown),
+                    '#' => Ok(C::Operational),
+                    '.' => Ok(C::Broken),
+                    _ => Err(src.error(&map[i..], "`?`, `#` or `.`")),
                 })
-                .collect();
+                .collect::<Result<_, ParseError>>()?;
 
             let operationals = info
                 .split(",")
-                .map(|n| n.parse::<usize>().unwrap())
-                .collect();
+                .map(|n| src.number(n))
+                .collect::<Result<_, ParseError>>()?;
 
-            Line {
+            Ok(Line {
                 springs,
                 operationals,
-            }
+            })
         })
         .collect()
 }
 
-fn matches_info(map: &Vec<&C>, info: &Vec<usize>) -> bool {
-    let mut index = 0;
-
+/// Whether the operational springs, `true` in `map`, are grouped the way `info` says.
+fn matches_info(map: &[bool], info: &[usize]) -> bool {
     let st = map
         .iter()
-        .map(|c| match c {
-            C::Operational => '#',
-            C::Broken => '.',
-            C::Unknown => panic!("SHouldn't contain unknowns anymore"),
-        })
+        .map(|&operational| if operational { '#' } else { '.' })
         .fold(String::new(), |mut acc, elem| {
             acc.push(elem);
             acc
@@ -66,29 +61,38 @@ fn matches_info(map: &Vec<&C>, info: &Vec<usize>) -> bool {
         .map(|c| c.len())
         .collect::<Vec<usize>>();
 
-    &nums == info
+    nums == info
 }
 
-pub fn solve_part_one<'a>(input: &'a str) -> String {
-    let input = parse_part_one(input);
-
+pub fn solve_part_one(input: Vec<Line>) -> Result<Answer, SolveError> {
     let mut sum = 0;
     for line in input.iter() {
-        let mut alts: Vec<Vec<&C>> =
-            Vec::with_capacity(1 << line.springs.iter().filter(|c| c == &&C::Unknown).count());
+        // Every unknown spring doubles the arrangements to try.
+        let unknowns = line.springs.iter().filter(|c| c == &&C::Unknown).count();
+        let arrangements = u32::try_from(unknowns)
+            .ok()
+            .and_then(|unknowns| 1usize.checked_shl(unknowns))
+            .ok_or_else(|| {
+                SolveError::UnsupportedInputShape(format!(
+                    "{unknowns} unknown springs on one line are too many to try every arrangement"
+                ))
+            })?;
+        let mut alts: Vec<Vec<bool>> = Vec::with_capacity(arrangements);
         alts.push(Vec::with_capacity(line.springs.len()));
 
         for spring in line.springs.iter() {
-            if spring == &C::Unknown {
-                alts.iter_mut().for_each(|list| list.push(&C::Operational));
-
-                for i in 0..alts.len() {
-                    let mut new = alts[i].clone();
-                    new[alts[i].len() - 1] = &C::Broken;
-                    alts.push(new);
+            match spring {
+                C::Unknown => {
+                    alts.iter_mut().for_each(|list| list.push(true));
+
+                    for i in 0..alts.len() {
+                        let mut new = alts[i].clone();
+                        new[alts[i].len() - 1] = false;
+                        alts.push(new);
+                    }
                 }
-            } else {
-                alts.iter_mut().for_each(|list| list.push(spring));
+                C::Operational => alts.iter_mut().for_each(|list| list.push(true)),
+                C::Broken => alts.iter_mut().for_each(|list| list.push(false)),
             }
         }
 
@@ -99,67 +103,43 @@ pub fn solve_part_one<'a>(input: &'a str) -> String {
             .count();
     }
 
-    sum.to_string()
+    Ok(sum.into())
 }
 
-fn parse_part_two<'a>(input: &'a str) -> Vec<Line> {
-    input
-        .lines()
-        .map(|l| {
-            let (map, info) = l.split_once(" ").unwrap();
-
-            let mut n_map = String::with_capacity(map.len() * 5 + 5);
-            let mut n_info = String::with_capacity(info.len() * 5 + 4);
-            for i in 0..5 {
-                n_map.push_str(map);
-                n_info.push_str(info);
-                if i == 4 {
-                    n_map.push('.');
-                } else {
-                    n_info.push(',');
-                    n_map.push('?');
-                }
-            }
-
-            let springs = n_map
-                .chars()
-                .map(|c| match c {
-                    '?' => C::Unknown,
-                    '#' => C::Operational,
-                    '.' => C::Broken,
-                    _ => panic!("Invalid input char {c}"),
-                })
-                .collect();
-
-            let operationals = n_info
-                .split(",")
-                .map(|n| n.parse::<usize>().unwrap())
-                .collect();
+/// Repeats the springs five times separated by unknowns, and the info five times.
+fn unfold(line: Line) -> Line {
+    let mut springs = Vec::with_capacity(line.springs.len() * 5 + 5);
+    for i in 0..5 {
+        springs.extend(line.springs.iter().cloned());
+        if i == 4 {
+            springs.push(C::Broken);
+        } else {
+            springs.push(C::Unknown);
+        }
+    }
 
-            Line {
-                springs,
-                operationals,
-            }
-        })
-        .collect()
+    Line {
+        springs,
+        operationals: line.operationals.repeat(5),
+    }
 }
 
-pub fn solve_part_two<'a>(input: &'a str) -> String {
-    let input = parse_part_two(input);
+pub fn solve_part_two(input: Vec<Line>) -> Answer {
+    let input = input.into_iter().map(unfold);
 
     let mut sum = 0;
-    for line in input.iter() {
+    for line in input {
         let mut map = HashMap::new();
         sum += count(&mut map, &line.springs, &line.operationals, 0, 0, 0);
     }
 
-    sum.to_string()
+    sum.into()
 }
 
 fn count(
     previous: &mut HashMap<(usize, usize, usize), usize>,
-    springs: &Vec<C>,
-    info: &Vec<usize>,
+    springs: &[C],
+    info: &[usize],
     index: usize,
     operational_index: usize,
     finished_operationals: usize,
@@ -167,7 +147,7 @@ fn count(
     let key = (index, operational_index, finished_operationals);
 
     if previous.contains_key(&key) {
-        return previous[&key].clone();
+        return previous[&key];
     }
 
     let mut val: usize = 0;
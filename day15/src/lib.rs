@@ -0,0 +1,51 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed<'a> = Vec<solution::Step<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_one(input))
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_two(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError, Solution};
+
+    use super::Day15;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day15>(1, input), Ok(Answer::Integer(1320)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day15>(2, input), Ok(Answer::Integer(145)));
+    }
+
+    #[test]
+    fn rejects_malformed_steps() {
+        let expected = |column| Err(ParseError::new(1, column, "`=<digit>` or `-`"));
+        assert_eq!(Day15::parse("rn=1,ab").map(|_| ()), expected(8));
+        assert_eq!(Day15::parse("rn=x").map(|_| ()), expected(3));
+        assert_eq!(
+            Day15::parse("").map(|_| ()),
+            Err(ParseError::new(1, 1, "a label"))
+        );
+    }
+}
@@ -1,88 +1,89 @@
-use std::collections::HashMap;
+use common::{Answer, ParseError, Source};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Remove,
+    Insert(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Step<'a> {
+    /// The whole step as written, which is what part one hashes.
+    text: &'a str,
+    label: &'a str,
+    operation: Operation,
+}
+
+pub fn parse(input: &str) -> Result<Vec<Step<'_>>, ParseError> {
+    let src = Source::new(input);
 
-pub fn parse<'a>(input: &str) -> Vec<Vec<u8>> {
     input
-        .replace("\n", "")
+        .trim_end_matches(['\n', '\r'])
         .split(",")
-        .map(|seq| seq.bytes().collect())
+        .map(|text| {
+            let (label, operation) = text.split_at(text.find(['=', '-']).unwrap_or(text.len()));
+            if label.is_empty() {
+                return Err(src.error(text, "a label"));
+            }
+
+            let operation = match operation.as_bytes() {
+                [b'-'] => Operation::Remove,
+                [b'=', d] if d.is_ascii_digit() => Operation::Insert((d - b'0') as usize),
+                _ => return Err(src.error(operation, "`=<digit>` or `-`")),
+            };
+
+            Ok(Step {
+                text,
+                label,
+                operation,
+            })
+        })
         .collect()
 }
 
 fn hash(bytes: &[u8]) -> usize {
     let mut current_value: usize = 0;
-    for b in bytes.into_iter() {
+    for b in bytes.iter() {
         current_value += *b as usize;
         current_value *= 17;
-        current_value = current_value % 256;
+        current_value %= 256;
     }
     current_value
 }
 
-pub fn solve_part_one<'a>(input: Vec<Vec<u8>>) -> String {
+pub fn solve_part_one(input: Vec<Step>) -> Answer {
     let mut sum: usize = 0;
 
-    for seq in input.into_iter() {
-        sum += hash(seq.as_slice());
+    for step in input.into_iter() {
+        sum += hash(step.text.as_bytes());
     }
 
-    sum.to_string()
+    sum.into()
 }
 
-pub fn solve_part_two<'a>(input: Vec<Vec<u8>>) -> String {
-    let mut boxes: HashMap<usize, Vec<(String, usize)>> = HashMap::new();
-
-    for seq in input.into_iter() {
-        if seq.last().unwrap() == &b'-' {
-            let label = &seq[0..seq.len() - 1];
-            let box_num = hash(label);
-
-            if let Some(v) = boxes.remove(&box_num) {
-                let label_str = String::from_utf8(label.to_vec()).unwrap();
-
-                boxes.insert(
-                    box_num.clone(),
-                    v.into_iter().filter(|(k, _)| k != &label_str).collect(),
-                );
-            }
-        } else {
-            let st = String::from_utf8(seq).unwrap();
-            let (label, value) = st.split_once("=").unwrap();
-            let box_num = hash(label.as_bytes());
-            let value: usize = value.parse().unwrap();
-
-            let mut v = if let Some(v) = boxes.remove(&box_num) {
-                v
-            } else {
-                vec![]
-            };
+pub fn solve_part_two(input: Vec<Step>) -> Answer {
+    let mut boxes: Vec<Vec<(&str, usize)>> = vec![vec![]; 256];
 
-            let mut contained = false;
-            v = v
-                .into_iter()
-                .map(|(k, v)| {
-                    if k == label {
-                        contained = true;
-                        (k, value)
-                    } else {
-                        (k, v)
-                    }
-                })
-                .collect();
+    for step in input.into_iter() {
+        let lenses = &mut boxes[hash(step.label.as_bytes())];
+        let slot = lenses.iter().position(|(label, _)| *label == step.label);
 
-            if !contained {
-                v.push((label.to_string(), value));
+        match (step.operation, slot) {
+            (Operation::Remove, Some(slot)) => {
+                lenses.remove(slot);
             }
-
-            boxes.insert(box_num, v);
+            (Operation::Remove, None) => {}
+            (Operation::Insert(focal_length), Some(slot)) => lenses[slot].1 = focal_length,
+            (Operation::Insert(focal_length), None) => lenses.push((step.label, focal_length)),
         }
     }
 
     let mut sum = 0;
-    for (k, v) in boxes.into_iter() {
+    for (k, v) in boxes.into_iter().enumerate() {
         for (slot, (_, val)) in v.into_iter().enumerate() {
             sum += (1 + k) * (slot + 1) * val;
         }
     }
 
-    sum.to_string()
+    sum.into()
 }
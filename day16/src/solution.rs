@@ -1,140 +1,53 @@
 use std::collections::HashSet;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Dir {
-    Up,
-    Right,
-    Down,
-    Left,
-}
+use common::{Answer, Direction, Grid, ParseError, Point, Source};
 
-impl Dir {
-    fn get_next(&self, x: usize, y: usize, map: &Vec<Vec<char>>) -> Option<(usize, usize)> {
-        match self {
-            Dir::Up => {
-                if y == 0 {
-                    return None;
-                }
-                return Some((x, y - 1));
-            }
-            Dir::Right => {
-                if x == map[0].len() - 1 {
-                    return None;
-                }
-                return Some((x + 1, y));
-            }
-            Dir::Down => {
-                if y == map.len() - 1 {
-                    return None;
-                }
-                return Some((x, y + 1));
-            }
-            Dir::Left => {
-                if x == 0 {
-                    return None;
-                }
-                return Some((x - 1, y));
-            }
-        }
-    }
-
-    // Handle '/'
-    fn tilt_right(&self) -> Dir {
-        match self {
-            Dir::Up => Dir::Right,
-            Dir::Right => Dir::Up,
-            Dir::Down => Dir::Left,
-            Dir::Left => Dir::Down,
-        }
-    }
+fn run_for_entrance(start: (Point, Direction), map: &Grid<char>) -> usize {
+    let mut to_check: Vec<(Point, Direction)> = Vec::new();
+    let mut splits: HashSet<Point> = HashSet::new();
 
-    // Handle '\'
-    fn tilt_left(&self) -> Dir {
-        match self {
-            Dir::Up => Dir::Left,
-            Dir::Right => Dir::Down,
-            Dir::Down => Dir::Right,
-            Dir::Left => Dir::Up,
-        }
-    }
-
-    // Handle '-'
-    fn handle_horizontal_split(&self) -> Option<(Dir, Dir)> {
-        match self {
-            Dir::Up => Some((Dir::Left, Dir::Right)),
-            Dir::Right => None,
-            Dir::Down => Some((Dir::Left, Dir::Right)),
-            Dir::Left => None,
-        }
-    }
-
-    // Handle '|'
-    fn handle_vertical_split(&self) -> Option<(Dir, Dir)> {
-        match self {
-            Dir::Up => None,
-            Dir::Right => Some((Dir::Up, Dir::Down)),
-            Dir::Down => None,
-            Dir::Left => Some((Dir::Up, Dir::Down)),
-        }
-    }
-}
-
-fn run_for_entrance(start: (usize, usize, Dir), map: &Vec<Vec<char>>) -> usize {
-    let mut to_check: Vec<(usize, usize, Dir)> = Vec::new();
-    let mut splits: HashSet<(usize, usize)> = HashSet::new();
-
-    let mut energized: HashSet<(usize, usize)> = HashSet::new();
+    let mut energized: HashSet<Point> = HashSet::new();
 
     to_check.push(start);
 
-    while to_check.len() > 0 {
-        let (mut x, mut y, mut going_in) = to_check.swap_remove(0);
+    while !to_check.is_empty() {
+        let (mut p, mut going_in) = to_check.swap_remove(0);
         'beam: loop {
-            match map[y][x] {
+            match map[p] {
                 '.' => {}
                 '/' => {
-                    going_in = going_in.tilt_right();
+                    going_in = going_in.reflect_slash();
                 }
                 '\\' => {
-                    going_in = going_in.tilt_left();
+                    going_in = going_in.reflect_backslash();
                 }
-                '|' => {
-                    if let Some((dir_a, dir_b)) = going_in.handle_vertical_split() {
-                        if splits.contains(&(x, y)) {
+                // A splitter hit on its flat side sends the beam off to both sides.
+                splitter @ ('|' | '-') => {
+                    let flat_side = if splitter == '|' {
+                        going_in.is_horizontal()
+                    } else {
+                        going_in.is_vertical()
+                    };
+                    if flat_side {
+                        if splits.contains(&p) {
                             // In an inf loop.
                             break 'beam;
                         }
-                        splits.insert((x, y));
+                        splits.insert(p);
 
-                        going_in = dir_a;
-                        if let Some((new_x, new_y)) = dir_b.get_next(x, y, map) {
-                            to_check.push((new_x, new_y, dir_b));
+                        let split = going_in.turn_right();
+                        going_in = going_in.turn_left();
+                        if let Some(next) = map.step(p, split) {
+                            to_check.push((next, split));
                         }
                     }
                 }
-                '-' => {
-                    if let Some((dir_a, dir_b)) = going_in.handle_horizontal_split() {
-                        if splits.contains(&(x, y)) {
-                            // In an inf loop.
-                            break 'beam;
-                        }
-                        splits.insert((x, y));
-
-                        going_in = dir_a;
-                        if let Some((new_x, new_y)) = dir_b.get_next(x, y, map) {
-                            to_check.push((new_x, new_y, dir_b));
-                        }
-                    }
-                }
-                v => {
-                    panic!("Invalid input '{v:?}'");
-                }
+                v => unreachable!("parsing only lets through the tiles above, not {v:?}"),
             }
 
-            energized.insert((x, y));
-            if let Some((new_x, new_y)) = going_in.get_next(x, y, map) {
-                x = new_x;
-                y = new_y;
+            energized.insert(p);
+            if let Some(next) = map.step(p, going_in) {
+                p = next;
             } else {
                 break 'beam;
             }
@@ -144,43 +57,43 @@ fn run_for_entrance(start: (usize, usize, Dir), map: &Vec<Vec<char>>) -> usize {
     energized.len()
 }
 
-pub fn parse<'a>(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|l| l.chars().collect()).collect()
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Source::new(input).char_grid(input, ".|-/\\")
 }
 
-pub fn solve_part_one<'a>(input: Vec<Vec<char>>) -> String {
-    run_for_entrance((0, 0, Dir::Right), &input).to_string()
+pub fn solve_part_one(input: Grid<char>) -> Answer {
+    run_for_entrance((Point::new(0, 0), Direction::Right), &input).into()
 }
 
-pub fn solve_part_two<'a>(input: Vec<Vec<char>>) -> String {
+pub fn solve_part_two(input: Grid<char>) -> Answer {
     let mut highest = 0;
 
-    let height = input.len();
-    let width = input[0].len();
+    let height = input.height();
+    let width = input.width();
 
     for y in 0..height {
-        let num = run_for_entrance((0, y, Dir::Right), &input);
+        let num = run_for_entrance((Point::new(0, y), Direction::Right), &input);
         if num > highest {
             highest = num;
         }
 
-        let num = run_for_entrance((width - 1, y, Dir::Left), &input);
+        let num = run_for_entrance((Point::new(width - 1, y), Direction::Left), &input);
         if num > highest {
             highest = num;
         }
     }
 
     for x in 0..width {
-        let num = run_for_entrance((x, 0, Dir::Down), &input);
+        let num = run_for_entrance((Point::new(x, 0), Direction::Down), &input);
         if num > highest {
             highest = num;
         }
 
-        let num = run_for_entrance((x, height - 1, Dir::Up), &input);
+        let num = run_for_entrance((Point::new(x, height - 1), Direction::Up), &input);
         if num > highest {
             highest = num;
         }
     }
 
-    highest.to_string()
+    highest.into()
 }
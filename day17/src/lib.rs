@@ -0,0 +1,90 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed<'a> = common::Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer};
+
+    use super::{solution, Day17};
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day17>(1, input), Ok(Answer::Integer(102)));
+    }
+
+    #[test]
+    fn part_two_example_1() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day17>(2, input), Ok(Answer::Integer(94)));
+    }
+
+    #[test]
+    fn part_two_example_2() {
+        let input = include_str!("../test_input2.txt");
+        assert_eq!(solve_example::<Day17>(2, input), Ok(Answer::Integer(71)));
+    }
+
+    #[test]
+    fn renders_the_route() {
+        let map = solution::parse(include_str!("../test_input2.txt")).unwrap();
+        let route = solution::path_find(&map, 4, 10).unwrap();
+
+        assert_eq!(route.heat_loss, 71);
+        assert_eq!(
+            route.heat_loss,
+            route.path.iter().map(|&(p, _)| map[p] as usize).sum()
+        );
+        assert_eq!(
+            solution::render(&map, &route),
+            "1>>>>>>>1111\n\
+             9999999v9991\n\
+             9999999v9991\n\
+             9999999v9991\n\
+             9999999v>>>>"
+        );
+    }
+
+    #[test]
+    fn renders_the_part_one_route() {
+        let map = solution::parse(include_str!("../test_input.txt")).unwrap();
+        let route = solution::path_find(&map, 1, 3).unwrap();
+
+        assert_eq!(route.heat_loss, 102);
+        assert_eq!(
+            solution::render(&map, &route),
+            "2>>34^>>>1323\n\
+             32v>>>35v5623\n\
+             32552456v>>54\n\
+             3446585845v52\n\
+             4546657867v>6\n\
+             14385987984v4\n\
+             44578769877v6\n\
+             36378779796v>\n\
+             465496798688v\n\
+             456467998645v\n\
+             12246868655<v\n\
+             25465488877v5\n\
+             43226746555v>"
+        );
+    }
+}
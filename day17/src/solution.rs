@@ -1,116 +1,84 @@
-use std::collections::HashMap;
+use common::{search, Answer, Direction, Grid, ParseError, Point, SolveError, Source};
 
-use priority_queue::PriorityQueue;
-
-pub fn parse<'a>(input: &str) -> Vec<Vec<u8>> {
-    input
-        .lines()
-        .map(|l| l.bytes().map(|c| c - 48).collect())
-        .collect()
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    Source::new(input).grid(input, "a digit", |c| c.to_digit(10).map(|d| d as u8))
 }
 
-#[derive(Debug, Clone, PartialEq, Hash, Eq, PartialOrd, Ord)]
-enum Dir {
-    Up,
-    Right,
-    Down,
-    Left,
+/// The way a crucible takes across the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub heat_loss: usize,
+    /// Every block entered after the top left corner, with the direction it was entered in.
+    pub path: Vec<(Point, Direction)>,
 }
 
-impl Dir {
-    fn opposite(&self) -> Dir {
-        match self {
-            Dir::Up => Dir::Down,
-            Dir::Right => Dir::Left,
-            Dir::Down => Dir::Up,
-            Dir::Left => Dir::Right,
+/// Finds the way from the top left to the bottom right corner losing the least heat, moving
+/// `min_steps` to `max_steps` blocks in a line before turning.
+///
+/// A state is a block along with the direction the crucible arrived in, so the next move turns.
+pub fn path_find(map: &Grid<u8>, min_steps: isize, max_steps: isize) -> Result<Route, SolveError> {
+    let start = Point::new(0, 0);
+    let goal = Point::new(map.width() - 1, map.height() - 1);
+
+    let moves = |&(p, dir): &(Point, Direction)| {
+        let mut moves = vec![];
+        for turn in [dir.turn_left(), dir.turn_right()] {
+            let (dx, dy) = turn.delta();
+            let mut cost = 0;
+            for step in 1..=max_steps {
+                let Some(next) = map.offset(p, dx * step, dy * step) else {
+                    break;
+                };
+                cost += map[next] as usize;
+                if step >= min_steps {
+                    moves.push(((next, turn), cost));
+                }
+            }
         }
-    }
-
-    fn left_right(&self) -> Vec<Dir> {
-        let dirs = vec![Dir::Up, Dir::Right, Dir::Down, Dir::Left];
-        dirs.into_iter()
-            .filter(|d| self != d)
-            .filter(|d| &self.opposite() != d)
-            .collect()
-    }
-
-    fn delta_x(&self) -> i32 {
-        match self {
-            Dir::Up => 0,
-            Dir::Right => 1,
-            Dir::Down => 0,
-            Dir::Left => -1,
+        moves
+    };
+
+    let found = search::dijkstra(
+        [(start, Direction::Right), (start, Direction::Down)],
+        moves,
+        |&(p, _)| p == goal,
+    )
+    .ok_or_else(|| {
+        SolveError::Unsolvable(format!(
+            "no path reaches the bottom right corner taking {min_steps} to {max_steps} steps at a time"
+        ))
+    })?;
+
+    // The search moves several blocks at a time, so fill in the blocks passed on the way.
+    let mut path = vec![];
+    for pair in found.path.windows(2) {
+        let (mut p, _) = pair[0];
+        let (end, dir) = pair[1];
+        while p != end {
+            p = map.step(p, dir).unwrap();
+            path.push((p, dir));
         }
     }
 
-    fn delta_y(&self) -> i32 {
-        match self {
-            Dir::Up => -1,
-            Dir::Right => 0,
-            Dir::Down => 1,
-            Dir::Left => 0,
-        }
-    }
+    Ok(Route {
+        heat_loss: found.cost,
+        path,
+    })
 }
 
-fn path_find(map: &Vec<Vec<u8>>, min_steps: i32, max_steps: i32) -> usize {
-    let mut to_check: PriorityQueue<((usize, usize), Dir), i64> = PriorityQueue::new();
-    to_check.push(((0, 0), Dir::Right), 0);
-    to_check.push(((0, 0), Dir::Down), 0);
-
-    let mut dists: HashMap<((usize, usize), Dir), usize> = HashMap::new();
-
-    let height = map.len() - 1;
-    let width = map[0].len() - 1;
-
-    while let Some((((x, y), dir), cost)) = to_check.pop() {
-        // Goal check
-        if y == height && x == width {
-            return (-cost) as usize;
-        }
-
-        // Prevent loops
-        if dists
-            .get(&((x, y), dir.clone()))
-            .is_some_and(|&alt_cost| (-cost) as usize > alt_cost)
-        {
-            continue;
-        }
-
-        // Check neighbours
-        for neighbour in dir.left_right() {
-            let mut new_cost = (-cost) as usize;
-            for step in 1..=max_steps {
-                let new_x = x as i32 + neighbour.delta_x() * step;
-                let new_y = y as i32 + neighbour.delta_y() * step;
-                if new_x < 0 || new_y < 0 {
-                    continue;
-                }
-
-                let new_x = new_x as usize;
-                let new_y = new_y as usize;
-                if new_x > width || new_y > height {
-                    continue;
-                }
-
-                new_cost += map[new_y][new_x] as usize;
-
-                let entry = ((new_x, new_y), neighbour.clone());
-                if &new_cost < dists.get(&entry).unwrap_or(&usize::MAX) && step >= min_steps {
-                    dists.insert(entry.clone(), new_cost);
-                    to_check.push(entry, -(new_cost as i64));
-                }
-            }
-        }
+/// Draws the map with an arrow on every block along `route`, as in the puzzle text.
+pub fn render(map: &Grid<u8>, route: &Route) -> String {
+    let mut drawing = map.map(|&heat| char::from(b'0' + heat));
+    for &(p, dir) in &route.path {
+        drawing[p] = dir.arrow();
     }
-    panic!("Found no path :(");
+    drawing.to_string()
 }
 
-pub fn solve_part_one<'a>(input: Vec<Vec<u8>>) -> String {
-    path_find(&input, 1, 3).to_string()
+pub fn solve_part_one(input: Grid<u8>) -> Result<Answer, SolveError> {
+    Ok(path_find(&input, 1, 3)?.heat_loss.into())
 }
 
-pub fn solve_part_two<'a>(input: Vec<Vec<u8>>) -> String {
-    path_find(&input, 4, 10).to_string()
+pub fn solve_part_two(input: Grid<u8>) -> Result<Answer, SolveError> {
+    Ok(path_find(&input, 4, 10)?.heat_loss.into())
 }
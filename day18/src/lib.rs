@@ -0,0 +1,65 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed<'a> = Vec<solution::Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_one(input))
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_two(input))
+    }
+
+    fn svg(input: Self::Parsed<'_>, part: u8) -> Option<Result<String, SolveError>> {
+        Some(Ok(solution::svg(&input, part)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer};
+
+    use super::{solution, Day18};
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_file.txt");
+        assert_eq!(solve_example::<Day18>(1, input), Ok(Answer::Integer(62)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_file.txt");
+        assert_eq!(
+            solve_example::<Day18>(2, input),
+            Ok(Answer::Integer(952408144115))
+        );
+    }
+
+    #[test]
+    fn draws_the_trench() {
+        let plan = solution::parse(include_str!("../test_file.txt")).unwrap();
+        let svg = solution::svg(&plan, 1);
+
+        assert!(svg.contains(
+            r##"<polygon points="0,0 6,0 6,5 4,5 4,7 6,7 6,9 1,9 1,7 0,7 0,5 2,5 2,2 0,2 0,0" fill="#e8e8e8""##
+        ));
+        // Each edge is drawn in the colour of the instruction that dug it.
+        let edges = svg
+            .lines()
+            .filter(|line| line.trim_start().starts_with("<line"))
+            .collect::<Vec<&str>>();
+        assert_eq!(edges.len(), 14);
+        assert!(edges[0].contains(r##"x1="0" y1="0" x2="6" y2="0" stroke="#70c710""##));
+        assert!(edges[13].contains(r##"x1="0" y1="2" x2="0" y2="0" stroke="#7a21e3""##));
+    }
+}
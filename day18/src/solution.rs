@@ -1,284 +1,102 @@
-use std::collections::HashSet;
-
-#[derive(Debug, Clone)]
-pub enum Dir {
-    Up,
-    Right,
-    Down,
-    Left,
-}
+use common::{
+    geometry::polygon::{self, Vertex},
+    svg::Svg,
+    Answer, Direction, ParseError, Source,
+};
 
 pub struct Instruction {
-    dir: Dir,
+    dir: Direction,
     steps: i64,
     hex_code: String,
 }
 
-pub fn parse<'a>(input: &str) -> Vec<Instruction> {
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let src = Source::new(input);
+
     input
         .lines()
         .map(|l| {
-            let (dir, res) = l.split_once(" ").unwrap();
-            let (steps, hex_code) = res.split_once(" ").unwrap();
+            let (dir, res) = src.split_once(l, " ")?;
+            let (steps, hex_code) = src.split_once(res, " ")?;
+            let hex_code = src.strip_suffix(src.strip_prefix(hex_code, "(#")?, ")")?;
 
-            Instruction {
-                dir: match dir {
-                    "R" => Dir::Right,
-                    "U" => Dir::Up,
-                    "D" => Dir::Down,
-                    "L" => Dir::Left,
-                    _ => panic!("Invalid dir {dir}"),
-                },
-                steps: steps.parse().unwrap(),
-                hex_code: hex_code
-                    .strip_prefix("(#")
-                    .unwrap()
-                    .strip_suffix(")")
-                    .unwrap()
-                    .to_string(),
+            if hex_code.len() != 6 || !hex_code.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(src.error(hex_code, "six hex digits"));
             }
+            if !('0'..='3').contains(&hex_code.chars().nth(5).unwrap()) {
+                return Err(src.error(&hex_code[5..], "a direction digit `0`-`3`"));
+            }
+
+            Ok(Instruction {
+                dir: match dir.parse().ok().and_then(Direction::from_letter) {
+                    Some(dir) => dir,
+                    None => return Err(src.error(dir, "`R`, `U`, `D` or `L`")),
+                },
+                steps: src.number(steps)?,
+                hex_code: hex_code.to_string(),
+            })
         })
         .collect()
 }
 
-fn print_map(map: &Vec<Vec<bool>>) {
-    let m = map
+/// The direction and length of every edge of the trench, as the given part reads the plan.
+fn read_plan(input: &[Instruction], part: u8) -> Vec<(Direction, i64)> {
+    input
         .iter()
-        .map(|l| {
-            l.iter()
-                .enumerate()
-                .filter(|(index, _)| index < &200)
-                .map(|(_, a)| a)
-                .map(|c| if *c { "#" } else { "." }.to_string())
-                .collect::<Vec<String>>()
-                .join("")
-        })
-        .collect::<Vec<String>>()
-        .join("\n");
-
-    println!("M:\n{m}");
-}
-
-fn determine_edges(instructions: &Vec<Instruction>) -> (i64, i64, i64, i64) {
-    let mut min_x = i64::MAX;
-    let mut max_x = i64::MIN;
-    let mut min_y = i64::MAX;
-    let mut max_y = i64::MIN;
-
-    let mut curr_x = 0;
-    let mut curr_y = 0;
-    for ins in instructions.iter() {
-        match ins.dir {
-            Dir::Up => {
-                curr_y -= ins.steps;
-                if curr_y < min_y {
-                    min_y = curr_y;
-                }
-            }
-            Dir::Right => {
-                curr_x += ins.steps;
-                if curr_x > max_x {
-                    max_x = curr_x;
-                }
-            }
-            Dir::Down => {
-                curr_y += ins.steps;
-                if curr_y > max_y {
-                    max_y = curr_y;
-                }
-            }
-            Dir::Left => {
-                curr_x -= ins.steps;
-                if curr_x < min_x {
-                    min_x = curr_x;
-                }
+        .map(|ins| {
+            if part == 1 {
+                return (ins.dir, ins.steps);
             }
-        }
-    }
-
-    (min_x, max_x, min_y, max_y)
-}
 
-fn create_map(
-    instructions: &Vec<Instruction>,
-    start_x: usize,
-    start_y: usize,
-    width: usize,
-    height: usize,
-) -> Vec<Vec<bool>> {
-    let mut curr_x = start_x;
-    let mut curr_y = start_y;
+            let steps = i64::from_str_radix(&ins.hex_code[0..5], 16).unwrap();
+            // The last digit was checked to be `0`-`3` while parsing.
+            let dir = Direction::from_digit(ins.hex_code.chars().nth(5).unwrap()).unwrap();
 
-    let mut map = vec![vec![false; width]; height];
-    map[curr_y][curr_x] = true;
-    for ins in instructions.iter() {
-        match ins.dir {
-            Dir::Up => {
-                let new_y = curr_y - ins.steps as usize;
-                for y in new_y..=curr_y {
-                    map[y][curr_x] = true;
-                }
-                curr_y = new_y;
-            }
-            Dir::Right => {
-                let new_x = curr_x + ins.steps as usize;
-                for x in curr_x..=new_x {
-                    map[curr_y][x] = true;
-                }
-                curr_x = new_x;
-            }
-            Dir::Down => {
-                let new_y = curr_y + ins.steps as usize;
-                for y in curr_y..=new_y {
-                    map[y][curr_x] = true;
-                }
-                curr_y = new_y;
-            }
-            Dir::Left => {
-                let new_x = curr_x - ins.steps as usize;
-                for x in new_x..=curr_x {
-                    map[curr_y][x] = true;
-                }
-                curr_x = new_x;
-            }
-        }
-    }
-    map
+            (dir, steps)
+        })
+        .collect()
 }
 
-fn flood_fill(
-    map: &Vec<Vec<bool>>,
-    width: usize,
-    height: usize,
-    start_x: usize,
-    start_y: usize,
-) -> HashSet<(usize, usize)> {
-    let mut checked = HashSet::new();
-    let mut to_check = HashSet::new();
-
-    to_check.insert((start_x + 1, start_y + 1));
-
-    while !to_check.is_empty() {
-        to_check = flood_fill_inner(&to_check, &mut checked, map, width, height);
+/// The corners of the trench, starting and ending where digging started.
+fn trench(plan: &[(Direction, i64)]) -> Vec<Vertex> {
+    let mut corner = (0, 0);
+    let mut corners = vec![corner];
+    for &(dir, steps) in plan {
+        let (dx, dy) = dir.delta();
+        corner = (corner.0 + dx as i64 * steps, corner.1 + dy as i64 * steps);
+        corners.push(corner);
     }
-
-    checked
+    corners
 }
 
-fn flood_fill_inner(
-    to_check: &HashSet<(usize, usize)>,
-    checked: &mut HashSet<(usize, usize)>,
-    map: &Vec<Vec<bool>>,
-    width: usize,
-    height: usize,
-) -> HashSet<(usize, usize)> {
-    let mut new_to_check = vec![];
-    for (x, y) in to_check.iter() {
-        let val = map[*y][*x];
-        if !val {
-            checked.insert((*x, *y));
-
-            if y > &0 {
-                new_to_check.push((*x, y - 1));
-            }
-            if *y < height - 1 {
-                new_to_check.push((*x, y + 1));
-            }
-            if *x > 0 {
-                new_to_check.push((x - 1, *y));
-            }
-            if *x < width - 1 {
-                new_to_check.push((x + 1, *y));
-            }
-        }
-    }
+/// The number of cubic metres dug out, counting both the trench and the ground it encloses.
+fn lagoon_size(plan: &[(Direction, i64)]) -> i64 {
+    let corners = trench(plan);
 
-    new_to_check
-        .into_iter()
-        .filter(|(x, y)| !checked.contains(&(*x, *y)))
-        .collect::<HashSet<(usize, usize)>>()
+    polygon::boundary_points(&corners) + polygon::interior_points(&corners)
 }
 
-pub fn solve_part_one<'a>(input: Vec<Instruction>) -> String {
-    let (min_x, max_x, min_y, max_y) = determine_edges(&input);
-
-    let width = (max_x - min_x + 1) as usize;
-    let height = (max_y - min_y + 1) as usize;
-
-    let mut map = create_map(
-        &input,
-        min_x.abs() as usize,
-        min_y.abs() as usize,
-        width,
-        height,
-    );
-
-    println!("Ended up with map of width {width} and height {height}");
-
-    let inner = flood_fill(
-        &map,
-        width,
-        height,
-        min_x.abs() as usize,
-        min_y.abs() as usize,
-    );
-
-    for (x, y) in inner.into_iter() {
-        map[y][x] = true;
-    }
+pub fn solve_part_one(input: Vec<Instruction>) -> Answer {
+    lagoon_size(&read_plan(&input, 1)).into()
+}
 
-    map.into_iter()
-        .map(|row| row.into_iter().filter(|c| *c).count())
-        .sum::<usize>()
-        .to_string()
+pub fn solve_part_two(input: Vec<Instruction>) -> Answer {
+    lagoon_size(&read_plan(&input, 2)).into()
 }
 
-pub fn solve_part_two<'a>(input: Vec<Instruction>) -> String {
-    let new_ins = input
+/// Draws the lagoon the given part digs, with every edge of the trench in its instruction's
+/// colour.
+pub fn svg(input: &[Instruction], part: u8) -> String {
+    let points = trench(&read_plan(input, part))
         .into_iter()
-        .map(|ins| {
-            let steps = i64::from_str_radix(&ins.hex_code[0..5], 16).unwrap();
-            let dir = match ins.hex_code.chars().nth(5).unwrap() {
-                '0' => Dir::Right,
-                '1' => Dir::Down,
-                '2' => Dir::Left,
-                '3' => Dir::Up,
-                _ => panic!("Invalid char"),
-            };
-
-            (dir, steps)
-        })
-        .collect::<Vec<(Dir, i64)>>();
+        .map(|(x, y)| (x as f64, y as f64))
+        .collect::<Vec<(f64, f64)>>();
 
-    let mut start_x = 0;
-    let mut start_y = 0;
-    let mut perim = vec![];
-    let mut perim_length = 0;
-
-    for (dir, steps) in new_ins.iter() {
-        let (end_x, end_y) = match dir {
-            Dir::Up => (start_x, start_y - steps),
-            Dir::Right => (start_x + steps, start_y),
-            Dir::Down => (start_x, start_y + steps),
-            Dir::Left => (start_x - steps, start_y),
-        };
-
-        perim_length += steps;
-        perim.push((end_x, end_y));
-        start_x = end_x;
-        start_y = end_y;
+    let mut svg = Svg::new();
+    svg.polygon(&points, "#e8e8e8", "none");
+    for (edge, ins) in points.windows(2).zip(input) {
+        svg.line(edge[0], edge[1], &format!("#{}", ins.hex_code), 3.0);
     }
 
-    (perim_length / 2
-        + perim
-            .windows(2)
-            .map(|l| {
-                let (a_x, a_y) = l[0];
-                let (b_x, b_y) = l[1];
-                (a_y + b_y) * (a_x - b_x)
-            })
-            .sum::<i64>()
-            / 2
-        + 1)
-    .to_string()
+    svg.to_string()
 }
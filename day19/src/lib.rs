@@ -0,0 +1,71 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed<'a> = solution::Workshop;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError, Solution, SolveError};
+
+    use super::Day19;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_file.txt");
+        assert_eq!(solve_example::<Day19>(1, input), Ok(Answer::Integer(19114)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_file.txt");
+        assert_eq!(
+            solve_example::<Day19>(2, input),
+            Ok(Answer::Integer(167409079868000))
+        );
+    }
+
+    #[test]
+    fn rejects_missing_workflows() {
+        assert_eq!(
+            Day19::parse("in{x<5:zz,A}\n\n{x=1,m=2,a=3,s=4}").map(|_| ()),
+            Err(ParseError::new(1, 8, "`A`, `R` or the name of a workflow"))
+        );
+        assert_eq!(
+            Day19::parse("px{x<5:A,R}\n\n{x=1,m=2,a=3,s=4}").map(|_| ()),
+            Err(ParseError::new(1, 12, "a workflow named `in`"))
+        );
+    }
+
+    #[test]
+    fn rejects_looping_workflows() {
+        let inputs = [
+            "in{x<5:in,in}\n\n{x=1,m=2,a=3,s=4}",
+            "in{x<5:b,A}\nb{m>1:c,R}\nc{a<4000:in,A}\n\n{x=1,m=2,a=3,s=4}",
+        ];
+        for input in inputs {
+            for part in 1..=2 {
+                assert!(matches!(
+                    solve_example::<Day19>(part, input),
+                    Err(SolveError::Unsolvable(_))
+                ));
+            }
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use common::{
+    interval::{HyperRect, Interval, IntervalSet},
+    Answer, ParseError, SolveError, Source,
+};
 
 #[derive(Debug, Clone)]
 pub struct Workshop {
@@ -6,17 +11,17 @@ pub struct Workshop {
     ratings: Vec<Part>,
 }
 
+/// The names of a part's ratings, in the order they're stored in.
+const CATEGORIES: [&str; 4] = ["x", "m", "a", "s"];
+
 #[derive(Debug, Clone)]
 pub struct Part {
-    x: usize,
-    m: usize,
-    a: usize,
-    s: usize,
+    ratings: [usize; 4],
 }
 
 impl Part {
     fn value(&self) -> usize {
-        self.x + self.m + self.a + self.s
+        self.ratings.iter().sum()
     }
 }
 
@@ -54,28 +59,35 @@ impl Action {
 
 #[derive(Debug, Clone)]
 pub struct Cond {
-    reg: String,
+    /// The index of the rating in [`CATEGORIES`].
+    reg: usize,
     val: usize,
     is_greater_than: bool, // If true is greater than, otherwise is less than.
 }
 
-pub fn parse<'a>(input: &str) -> Workshop {
-    let (workflows, ratings) = input.split_once("\n\n").unwrap();
+pub fn parse(input: &str) -> Result<Workshop, ParseError> {
+    let src = Source::new(input);
+
+    let (workflow_lines, ratings) = src.split_once(input, "\n\n")?;
 
-    let workflows = workflows
+    // Where every workflow that a rule sends parts to is named, to check that they all exist.
+    let mut targets = vec![];
+
+    let workflows: HashMap<String, Workflow> = workflow_lines
         .lines()
         .map(|workflow| {
-            let (name, rest) = workflow.split_once("{").unwrap();
+            let (name, rest) = src.split_once(workflow, "{")?;
 
-            let rest = rest.strip_suffix("}").unwrap();
+            let rest = src.strip_suffix(rest, "}")?;
 
-            let mut steps = rest
-                .split(",")
-                .map(|a| a.to_string())
-                .collect::<Vec<String>>();
+            // The last rule has no condition and applies when none of the others do.
+            let (steps, default) = match rest.rsplit_once(",") {
+                Some((steps, default)) => (steps.split(",").collect(), default),
+                None => (vec![], rest),
+            };
 
-            let default = steps.pop().unwrap();
-            let default = Action::parse(&default);
+            targets.push(default);
+            let default = Action::parse(default);
 
             let steps = steps
                 .into_iter()
@@ -86,76 +98,100 @@ pub fn parse<'a>(input: &str) -> Workshop {
                     } else if let Some((reg, cond)) = s.split_once(">") {
                         (reg, cond, true)
                     } else {
-                        panic!("Invalid step {s}");
+                        return Err(src.error_after(s, "`<` or `>`"));
                     };
 
-                    let (num, action) = rest.split_once(":").unwrap();
+                    let (num, action) = src.split_once(rest, ":")?;
 
-                    let val = num.parse::<usize>().unwrap();
+                    let Some(reg) = CATEGORIES.iter().position(|&name| name == reg) else {
+                        return Err(src.error(reg, "`x`, `m`, `a` or `s`"));
+                    };
+                    let val = src.number(num)?;
 
+                    targets.push(action);
                     let action = Action::parse(action);
 
-                    (
+                    Ok((
                         Cond {
-                            reg: reg.to_string(),
+                            reg,
                             val,
                             is_greater_than,
                         },
                         action,
-                    )
+                    ))
                 })
-                .collect();
+                .collect::<Result<_, ParseError>>()?;
 
-            Workflow {
+            Ok(Workflow {
                 name: name.to_string(),
                 steps,
                 default,
-            }
+            })
         })
-        .map(|w| (w.name.clone(), w))
-        .collect();
+        .map(|w| w.map(|w| (w.name.clone(), w)))
+        .collect::<Result<_, ParseError>>()?;
+
+    if !workflows.contains_key("in") {
+        return Err(src.error_after(workflow_lines, "a workflow named `in`"));
+    }
+    for target in targets {
+        if !matches!(target, "A" | "R") && !workflows.contains_key(target) {
+            return Err(src.error(target, "`A`, `R` or the name of a workflow"));
+        }
+    }
 
     let ratings = ratings
         .lines()
-        .map(|rating| {
-            let rating = rating.strip_prefix("{").unwrap().strip_suffix("}").unwrap();
+        .map(|line| {
+            let rating = src.strip_suffix(src.strip_prefix(line, "{")?, "}")?;
 
             let rating = rating
                 .split(",")
                 .map(|r| {
-                    let (reg, num) = r.split_once("=").unwrap();
+                    let (reg, num) = src.split_once(r, "=")?;
 
-                    (reg.to_string(), num.parse::<usize>().unwrap())
+                    Ok((reg, src.number(num)?))
                 })
-                .collect::<HashMap<String, usize>>();
-
-            Part {
-                x: rating["x"],
-                m: rating["m"],
-                a: rating["a"],
-                s: rating["s"],
-            }
+                .collect::<Result<HashMap<&str, usize>, ParseError>>()?;
+
+            let reg = |name| {
+                rating
+                    .get(name)
+                    .copied()
+                    .ok_or_else(|| src.error(line, format!("a rating for `{name}`")))
+            };
+
+            Ok(Part {
+                ratings: [reg("x")?, reg("m")?, reg("a")?, reg("s")?],
+            })
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    Workshop { workflows, ratings }
+    Ok(Workshop { workflows, ratings })
 }
 
-pub fn solve_part_one<'a>(input: Workshop) -> String {
+/// The error for parts that get sent around in a circle through `workflow`.
+fn loops_through(workflow: &str) -> SolveError {
+    SolveError::Unsolvable(format!(
+        "parts sent to `{workflow}` are sent back to it without being accepted or rejected"
+    ))
+}
+
+pub fn solve_part_one(input: Workshop) -> Result<Answer, SolveError> {
     let mut sum = 0;
 
     'ratings: for rating in input.ratings.iter() {
         let mut curr_reg_name = "in";
+        let mut visited = HashSet::new();
         'reg_loop: loop {
-            let curr_reg = input.workflows.get(curr_reg_name).unwrap();
+            // The part goes the same way every time, so a workflow seen again is a loop.
+            if !visited.insert(curr_reg_name) {
+                return Err(loops_through(curr_reg_name));
+            }
+            // Every workflow parts are sent to was checked to exist while parsing.
+            let curr_reg = &input.workflows[curr_reg_name];
             for (cond, action) in curr_reg.steps.iter() {
-                let compare_val = match cond.reg.as_str() {
-                    "x" => rating.x,
-                    "m" => rating.m,
-                    "a" => rating.a,
-                    "s" => rating.s,
-                    _ => panic!("Invalid reg {cond:?}"),
-                };
+                let compare_val = rating.ratings[cond.reg];
 
                 let accepted = if cond.is_greater_than {
                     compare_val > cond.val
@@ -192,171 +228,66 @@ pub fn solve_part_one<'a>(input: Workshop) -> String {
         }
     }
 
-    sum.to_string()
-}
-
-#[derive(Debug, Clone)]
-struct RegRanges {
-    min_x: usize,
-    max_x: usize,
-    min_m: usize,
-    max_m: usize,
-    min_a: usize,
-    max_a: usize,
-    min_s: usize,
-    max_s: usize,
-}
-
-impl RegRanges {
-    fn is_valid(&self) -> bool {
-        self.min_x <= self.max_x
-            && self.min_m <= self.max_m
-            && self.min_a <= self.max_a
-            && self.min_s <= self.max_s
-    }
-
-    fn to_val(&self) -> usize {
-        (self.max_x - self.min_x + 1)
-            * (self.max_m - self.min_m + 1)
-            * (self.max_a - self.min_a + 1)
-            * (self.max_s - self.min_s + 1)
-    }
-
-    fn initial() -> Self {
-        Self {
-            min_x: 1,
-            max_x: 4000,
-            min_m: 1,
-            max_m: 4000,
-            min_a: 1,
-            max_a: 4000,
-            min_s: 1,
-            max_s: 4000,
-        }
-    }
+    Ok(sum.into())
 }
 
-pub fn solve_part_two<'a>(input: Workshop) -> String {
-    let mut states: Vec<(RegRanges, &str, usize)> = vec![(RegRanges::initial(), "in", 0)];
+pub fn solve_part_two(input: Workshop) -> Result<Answer, SolveError> {
+    // Every rating is from 1 to 4000, one dimension per category.
+    let all_parts = HyperRect::new([Interval::inclusive(1, 4000); 4]);
+    // Along with each range of parts go the workflows it has been through.
+    let mut states: Vec<(HyperRect<4>, &str, usize, Vec<&str>)> =
+        vec![(all_parts, "in", 0, vec![])];
 
-    let mut accepted_ranges: Vec<RegRanges> = vec![];
+    let mut accepted = IntervalSet::new();
 
-    while let Some((regs, workflow_name, step_index)) = states.pop() {
+    while let Some((regs, workflow_name, step_index, mut visited)) = states.pop() {
         if workflow_name == "A" {
-            accepted_ranges.push(regs);
+            accepted.insert(regs);
             continue;
         } else if workflow_name == "R" {
             continue;
         }
 
-        if !regs.is_valid() {
+        if regs.is_empty() {
             continue;
         }
 
-        let workflow = input.workflows.get(workflow_name).unwrap();
+        if step_index == 0 {
+            if visited.contains(&workflow_name) {
+                return Err(loops_through(workflow_name));
+            }
+            visited.push(workflow_name);
+        }
+
+        // Every workflow parts are sent to was checked to exist while parsing.
+        let workflow = &input.workflows[workflow_name];
 
         // Handle default
         if step_index >= workflow.steps.len() {
             match &workflow.default {
                 Action::Reject => continue,
                 Action::Accept => {
-                    accepted_ranges.push(regs);
+                    accepted.insert(regs);
                     continue;
                 }
                 Action::SendTo(v) => {
-                    states.push((regs, v.as_str(), 0));
+                    states.push((regs, v.as_str(), 0, visited));
                     continue;
                 }
             }
         }
 
         let (cond, action) = &workflow.steps[step_index];
-        if cond.is_greater_than {
-            match cond.reg.as_str() {
-                "x" => {
-                    let mut lower_range = regs.clone();
-                    lower_range.min_x = cond.val + 1;
-                    states.push((lower_range, action.to_workflow_name(), 0));
-
-                    let mut higher_range = regs.clone();
-                    higher_range.max_x = cond.val;
-                    states.push((higher_range, workflow_name, step_index + 1));
-                }
-                "m" => {
-                    let mut lower_range = regs.clone();
-                    lower_range.min_m = cond.val + 1;
-                    states.push((lower_range, action.to_workflow_name(), 0));
-
-                    let mut higher_range = regs.clone();
-                    higher_range.max_m = cond.val;
-                    states.push((higher_range, workflow_name, step_index + 1));
-                }
-                "a" => {
-                    let mut lower_range = regs.clone();
-                    lower_range.min_a = cond.val + 1;
-                    states.push((lower_range, action.to_workflow_name(), 0));
-
-                    let mut higher_range = regs.clone();
-                    higher_range.max_a = cond.val;
-                    states.push((higher_range, workflow_name, step_index + 1));
-                }
-                "s" => {
-                    let mut lower_range = regs.clone();
-                    lower_range.min_s = cond.val + 1;
-                    states.push((lower_range, action.to_workflow_name(), 0));
-
-                    let mut higher_range = regs.clone();
-                    higher_range.max_s = cond.val;
-                    states.push((higher_range, workflow_name, step_index + 1));
-                }
-                _ => panic!("Invalid cond {cond:?}"),
-            }
+        let val = cond.val as i64;
+        let (matching, rest) = if cond.is_greater_than {
+            let (lower, higher) = regs.split_at(cond.reg, val + 1);
+            (higher, lower)
         } else {
-            match cond.reg.as_str() {
-                "x" => {
-                    let mut lower_range = regs.clone();
-                    lower_range.max_x = cond.val - 1;
-                    states.push((lower_range, action.to_workflow_name(), 0));
-
-                    let mut higher_range = regs.clone();
-                    higher_range.min_x = cond.val;
-                    states.push((higher_range, workflow_name, step_index + 1));
-                }
-                "m" => {
-                    let mut lower_range = regs.clone();
-                    lower_range.max_m = cond.val - 1;
-                    states.push((lower_range, action.to_workflow_name(), 0));
-
-                    let mut higher_range = regs.clone();
-                    higher_range.min_m = cond.val;
-                    states.push((higher_range, workflow_name, step_index + 1));
-                }
-                "a" => {
-                    let mut lower_range = regs.clone();
-                    lower_range.max_a = cond.val - 1;
-                    states.push((lower_range, action.to_workflow_name(), 0));
-
-                    let mut higher_range = regs.clone();
-                    higher_range.min_a = cond.val;
-                    states.push((higher_range, workflow_name, step_index + 1));
-                }
-                "s" => {
-                    let mut lower_range = regs.clone();
-                    lower_range.max_s = cond.val - 1;
-                    states.push((lower_range, action.to_workflow_name(), 0));
-
-                    let mut higher_range = regs.clone();
-                    higher_range.min_s = cond.val;
-                    states.push((higher_range, workflow_name, step_index + 1));
-                }
-                _ => panic!("Invalid cond {cond:?}"),
-            }
-        }
+            regs.split_at(cond.reg, val)
+        };
+        states.push((matching, action.to_workflow_name(), 0, visited.clone()));
+        states.push((rest, workflow_name, step_index + 1, visited));
     }
 
-    accepted_ranges
-        .iter()
-        .map(|range| range.to_val())
-        .sum::<usize>()
-        .to_string()
+    Ok(accepted.volume().into())
 }
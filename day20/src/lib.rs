@@ -0,0 +1,244 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed<'a> = std::collections::HashMap<String, solution::Module>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_one(input))
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+
+    fn dot(input: Self::Parsed<'_>) -> Option<String> {
+        Some(solution::to_dot(&input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, SolveError};
+
+    use super::{solution, Day20};
+
+    #[test]
+    fn part_one_example_1() {
+        let input = include_str!("../test_file.txt");
+        assert_eq!(
+            solve_example::<Day20>(1, input),
+            Ok(Answer::Integer(32000000))
+        );
+    }
+
+    #[test]
+    fn part_one_example_2() {
+        let input = include_str!("../test_file2.txt");
+        assert_eq!(
+            solve_example::<Day20>(1, input),
+            Ok(Answer::Integer(11687500))
+        );
+    }
+
+    #[test]
+    fn part_two_needs_rx() {
+        let input = include_str!("../test_file2.txt");
+        assert!(matches!(
+            solve_example::<Day20>(2, input),
+            Err(SolveError::UnsupportedInputShape(_))
+        ));
+    }
+
+    fn press(network: &mut solution::Network) -> Vec<String> {
+        network.press().iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn pulse_log() {
+        let modules = solution::parse(include_str!("../test_file.txt")).unwrap();
+        let mut network = solution::Network::new(&modules);
+
+        assert_eq!(
+            press(&mut network),
+            [
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "broadcaster -low-> b",
+                "broadcaster -low-> c",
+                "a -high-> b",
+                "b -high-> c",
+                "c -high-> inv",
+                "inv -low-> a",
+                "a -low-> b",
+                "b -low-> c",
+                "c -low-> inv",
+                "inv -high-> a",
+            ]
+        );
+    }
+
+    #[test]
+    fn state_and_reset() {
+        let modules = solution::parse(include_str!("../test_file2.txt")).unwrap();
+        let mut network = solution::Network::new(&modules);
+        let first = press(&mut network);
+
+        assert_eq!(
+            first,
+            [
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "a -high-> inv",
+                "a -high-> con",
+                "inv -low-> b",
+                "con -high-> output",
+                "b -high-> con",
+                "con -low-> output",
+            ]
+        );
+        assert!(network.state().flip_flops["a"]);
+        assert!(network.state().flip_flops["b"]);
+        assert_eq!(
+            network.state().conjunctions["con"]["a"],
+            solution::Pulse::High
+        );
+
+        assert_eq!(
+            press(&mut network),
+            [
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "a -low-> inv",
+                "a -low-> con",
+                "inv -high-> b",
+                "con -high-> output",
+            ]
+        );
+        assert!(!network.state().flip_flops["a"]);
+        assert_eq!(network.presses(), 2);
+
+        network.reset();
+        assert_eq!(network.presses(), 0);
+        assert_eq!(press(&mut network), first);
+    }
+
+    #[test]
+    fn dot_export() {
+        let modules = solution::parse(include_str!("../test_file2.txt")).unwrap();
+
+        assert_eq!(
+            solution::to_dot(&modules),
+            "digraph modules {
+  \"a\" [shape=box];
+  \"b\" [shape=box];
+  \"broadcaster\" [shape=doublecircle];
+  \"con\" [shape=diamond];
+  \"inv\" [shape=diamond];
+  subgraph cluster_0 {
+    label=\"from a, cycle of 4 after 0\";
+    \"a\";
+    \"b\";
+    \"con\";
+    \"inv\";
+  }
+  \"a\" -> \"inv\";
+  \"a\" -> \"con\";
+  \"b\" -> \"con\";
+  \"broadcaster\" -> \"a\";
+  \"con\" -> \"output\";
+  \"inv\" -> \"b\";
+}
+"
+        );
+    }
+
+    // Counters from `a` and `c` that reset after 3 and 2 presses, both feeding `hub`.
+    #[test]
+    fn finds_subcircuits() {
+        let modules = solution::parse(include_str!("../test_file3.txt")).unwrap();
+        let subcircuits = solution::subcircuits(&modules).unwrap();
+
+        let found = subcircuits
+            .iter()
+            .map(|sub| (sub.entry, sub.modules.len(), sub.cycle.period))
+            .collect::<Vec<_>>();
+        assert_eq!(found, [("a", 4, 3), ("c", 4, 2)]);
+        assert!(solution::to_dot(&modules).contains("label=\"from a, cycle of 3 after "));
+    }
+
+    // `hub` joins both counters, so it sending back into one makes them depend on each other.
+    #[test]
+    fn subcircuits_must_be_independent() {
+        let input = include_str!("../test_file3.txt").replace("&hub -> rx", "&hub -> rx, a");
+        let modules = solution::parse(&input).unwrap();
+
+        let err = solution::subcircuits(&modules).unwrap_err();
+        assert!(matches!(err, SolveError::UnsupportedInputShape(_)));
+        assert!(
+            solution::to_dot(&modules).contains(&format!("  label=\"no subcircuits, {err}\";\n"))
+        );
+    }
+
+    #[test]
+    fn part_two_counters() {
+        let input = include_str!("../test_file3.txt");
+        assert_eq!(solve_example::<Day20>(2, input), Ok(Answer::Integer(6)));
+    }
+
+    // `cc` now also waits for `e`, which only it can turn on, so the second counter never fires.
+    #[test]
+    fn part_two_never_low() {
+        let input =
+            include_str!("../test_file3.txt").replace("&cc -> ic, d", "&cc -> ic, d, e\n%e -> cc");
+        assert!(matches!(
+            solve_example::<Day20>(2, &input),
+            Err(SolveError::Unsolvable(_))
+        ));
+    }
+
+    // Nothing sends to `zz`, so it never sends `rx` anything, and `hub` is still the first to.
+    #[test]
+    fn part_two_parent_without_inputs() {
+        let input =
+            include_str!("../test_file3.txt").replace("&hub -> rx", "&hub -> rx\n&zz -> rx");
+        assert_eq!(solve_example::<Day20>(2, &input), Ok(Answer::Integer(6)));
+
+        let input =
+            include_str!("../test_file3.txt").replace("&hub -> rx", "&hub -> out\n&zz -> rx");
+        assert!(matches!(
+            solve_example::<Day20>(2, &input),
+            Err(SolveError::Unsolvable(_))
+        ));
+    }
+
+    #[test]
+    fn part_two_needs_independent_counters() {
+        let input = include_str!("../test_file3.txt").replace("%c -> d", "%c -> d, b");
+        assert!(matches!(
+            solve_example::<Day20>(2, &input),
+            Err(SolveError::UnsupportedInputShape(_))
+        ));
+    }
+
+    // Without resetting, `ic` sends `hub` a high pulse on every other press and a low one on the
+    // next, so `hub` remembers the high pulse in between.
+    #[test]
+    fn part_two_needs_counters_to_reset() {
+        let input = include_str!("../test_file3.txt")
+            .replace("%c -> d\n%d -> cc\n&cc -> ic, d", "%c -> cc\n&cc -> ic");
+        assert_eq!(
+            solve_example::<Day20>(2, &input),
+            Err(SolveError::UnsupportedInputShape(
+                "`ic` can leave `hub` remembering a high pulse after a press".to_string()
+            ))
+        );
+    }
+}
@@ -1,4 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    fmt::Display,
+};
+
+use common::{
+    cycle::{self, Cycle, Periodic},
+    search, Answer, ParseError, SolveError, Source,
+};
 
 #[derive(Debug, Clone)]
 pub struct Module {
@@ -14,11 +22,13 @@ pub enum ModuleType {
     FlipFlop,
 }
 
-pub fn parse<'a>(input: &str) -> HashMap<String, Module> {
+pub fn parse(input: &str) -> Result<HashMap<String, Module>, ParseError> {
+    let src = Source::new(input);
+
     input
         .lines()
         .map(|l| {
-            let (input, output) = l.split_once(" -> ").unwrap();
+            let (input, output) = src.split_once(l, " -> ")?;
             let (module_type, name) = if input == "broadcaster" {
                 (ModuleType::Brodcaster, input.to_string())
             } else if let Some(name) = input.strip_prefix("&") {
@@ -26,189 +36,506 @@ pub fn parse<'a>(input: &str) -> HashMap<String, Module> {
             } else if let Some(name) = input.strip_prefix("%") {
                 (ModuleType::FlipFlop, name.to_string())
             } else {
-                panic!("Invalid input {input:?}");
+                return Err(src.error(input, "`broadcaster`, `&` or `%`"));
             };
 
             let outputs = output.split(",").map(|s| s.trim().to_string()).collect();
 
-            (
+            Ok((
                 name.clone(),
                 Module {
                     name,
                     outputs,
                     module_type,
                 },
-            )
+            ))
         })
         .collect()
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum Pulse {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pulse {
     High,
     Low,
 }
 
-fn find_conjunction_inputs(
-    modules: &HashMap<String, Module>,
-) -> HashMap<&str, HashMap<&str, Pulse>> {
-    let mut map: HashMap<&str, HashMap<&str, Pulse>> = HashMap::new();
-
-    for (m, cont) in modules.iter() {
-        if cont.module_type == ModuleType::Conjunction {
-            map.insert(m, HashMap::new());
+impl Display for Pulse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pulse::High => f.write_str("high"),
+            Pulse::Low => f.write_str("low"),
         }
     }
+}
 
-    for (m, cont) in modules.iter() {
-        for out in cont.outputs.iter() {
-            if let Some(l) = map.get_mut(out.as_str()) {
-                l.insert(m, Pulse::Low);
-            }
-        }
+/// A pulse sent from one module to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signal<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub pulse: Pulse,
+}
+
+/// Prints the signal as in the puzzle text, e.g. `broadcaster -low-> a`.
+impl Display for Signal<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -{}-> {}", self.from, self.pulse, self.to)
     }
+}
 
-    map
+/// What every module remembers between button presses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct State<'a> {
+    /// Whether each flip-flop is on.
+    pub flip_flops: BTreeMap<&'a str, bool>,
+    /// The last pulse each conjunction got from each of its inputs.
+    pub conjunctions: BTreeMap<&'a str, BTreeMap<&'a str, Pulse>>,
 }
 
-fn find_flip_flop_states(modules: &HashMap<String, Module>) -> HashMap<&str, bool> {
-    let mut map: HashMap<&str, bool> = HashMap::new();
+impl<'a> State<'a> {
+    /// Every flip-flop off, and every conjunction remembering a low pulse from every input.
+    fn initial(modules: &'a HashMap<String, Module>) -> Self {
+        let mut flip_flops = BTreeMap::new();
+        let mut conjunctions = BTreeMap::new();
+
+        for (name, module) in modules.iter() {
+            match module.module_type {
+                ModuleType::FlipFlop => {
+                    flip_flops.insert(name.as_str(), false);
+                }
+                ModuleType::Conjunction => {
+                    conjunctions.insert(name.as_str(), BTreeMap::new());
+                }
+                ModuleType::Brodcaster => {}
+            }
+        }
+
+        for (name, module) in modules.iter() {
+            for out in module.outputs.iter() {
+                if let Some(inputs) = conjunctions.get_mut(out.as_str()) {
+                    inputs.insert(name.as_str(), Pulse::Low);
+                }
+            }
+        }
 
-    for (m, cont) in modules.iter() {
-        if cont.module_type == ModuleType::FlipFlop {
-            map.insert(m, false);
+        Self {
+            flip_flops,
+            conjunctions,
         }
     }
+}
 
-    map
+/// The modules wired together, along with their state after the presses so far.
+pub struct Network<'a> {
+    modules: &'a HashMap<String, Module>,
+    state: State<'a>,
+    presses: usize,
 }
 
-fn simulate(
-    modules: &HashMap<String, Module>,
-    conjunction_inputs: &mut HashMap<&str, HashMap<&str, Pulse>>,
-    flip_flop_states: &mut HashMap<&str, bool>,
-    iteration: usize,
-    rx_inputs: &mut HashMap<&str, usize>,
-) -> (usize, usize) {
-    let mut low_pulses = 0;
-    let mut high_pulses = 0;
-    let mut stack = vec![("button", "broadcaster", Pulse::Low)];
-
-    while stack.len() > 0 {
-        let (from, to, pulse) = stack.remove(0);
-        // println!("{from} -{pulse:?}-> {to}");
-        match pulse {
-            Pulse::High => high_pulses += 1,
-            Pulse::Low => {
-                low_pulses += 1;
-                if rx_inputs.contains_key(to) && rx_inputs[to] == 0 {
-                    *rx_inputs.get_mut(to).unwrap() = iteration;
-                }
-            }
-        };
+impl<'a> Network<'a> {
+    pub fn new(modules: &'a HashMap<String, Module>) -> Self {
+        Self {
+            modules,
+            state: State::initial(modules),
+            presses: 0,
+        }
+    }
 
-        let Some(cont) = modules.get(to) else {
-            continue;
-        };
+    /// Presses the button and lets the pulses settle, returning every pulse sent in the order
+    /// they arrived.
+    pub fn press(&mut self) -> Vec<Signal<'a>> {
+        let mut log = vec![];
+        let mut queue = VecDeque::from([Signal {
+            from: "button",
+            to: "broadcaster",
+            pulse: Pulse::Low,
+        }]);
+
+        while let Some(signal) = queue.pop_front() {
+            log.push(signal);
+            let Signal { from, to, pulse } = signal;
+
+            // Modules that are only ever sent to, like `output`, do nothing.
+            let Some((to, module)) = self.modules.get_key_value(to) else {
+                continue;
+            };
 
-        let out_pulse = match cont.module_type {
-            ModuleType::Brodcaster => Some(pulse),
-            ModuleType::Conjunction => {
-                let inps = conjunction_inputs.get_mut(to).unwrap();
-                *inps.get_mut(from).unwrap() = pulse;
-                if inps.values().all(|p| p == &Pulse::High) {
-                    Some(Pulse::Low)
-                } else {
-                    Some(Pulse::High)
+            let out_pulse = match module.module_type {
+                ModuleType::Brodcaster => Some(pulse),
+                ModuleType::Conjunction => {
+                    let inputs = self.state.conjunctions.get_mut(to.as_str()).unwrap();
+                    *inputs.get_mut(from).unwrap() = pulse;
+                    if inputs.values().all(|p| p == &Pulse::High) {
+                        Some(Pulse::Low)
+                    } else {
+                        Some(Pulse::High)
+                    }
                 }
-            }
-            ModuleType::FlipFlop => {
-                if pulse == Pulse::Low {
-                    let state = flip_flop_states[to];
-                    *flip_flop_states.get_mut(to).unwrap() = !state;
-
-                    Some(if state { Pulse::Low } else { Pulse::High })
-                } else {
-                    None
+                ModuleType::FlipFlop => {
+                    if pulse == Pulse::Low {
+                        let on = self.state.flip_flops.get_mut(to.as_str()).unwrap();
+                        *on = !*on;
+
+                        Some(if *on { Pulse::High } else { Pulse::Low })
+                    } else {
+                        None
+                    }
                 }
-            }
-        };
+            };
 
-        if let Some(p) = out_pulse {
-            for out in cont.outputs.iter() {
-                stack.push((to, out, p.clone()));
+            if let Some(pulse) = out_pulse {
+                for out in module.outputs.iter() {
+                    queue.push_back(Signal {
+                        from: to,
+                        to: out,
+                        pulse,
+                    });
+                }
             }
         }
+
+        self.presses += 1;
+        log
     }
 
-    (low_pulses, high_pulses)
+    pub fn state(&self) -> &State<'a> {
+        &self.state
+    }
+
+    /// How many times the button was pressed since the network was built or reset.
+    pub fn presses(&self) -> usize {
+        self.presses
+    }
+
+    /// Puts every module back in its initial state.
+    pub fn reset(&mut self) {
+        self.state = State::initial(self.modules);
+        self.presses = 0;
+    }
 }
 
-pub fn solve_part_one<'a>(input: HashMap<String, Module>) -> String {
-    let mut conjunction_inputs = find_conjunction_inputs(&input);
-    let mut flip_flop_state = find_flip_flop_states(&input);
+pub fn solve_part_one(input: HashMap<String, Module>) -> Answer {
+    let mut network = Network::new(&input);
     let (mut lows, mut highs) = (0, 0);
 
-    let mut ignored = HashMap::new();
+    for _ in 0..1000 {
+        for signal in network.press() {
+            match signal.pulse {
+                Pulse::High => highs += 1,
+                Pulse::Low => lows += 1,
+            }
+        }
+    }
+
+    (lows * highs).into()
+}
+
+/// How many presses a subcircuit gets for its state to repeat before it's taken to be something
+/// other than a counter.
+const MAX_PRESSES: usize = 1 << 16;
+
+/// The modules one of the broadcaster's outputs leads to on its own, which get pulses from
+/// nothing else.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subcircuit<'a> {
+    /// The broadcaster's output it starts at.
+    pub entry: &'a str,
+    pub modules: BTreeSet<&'a str>,
+    /// How its state repeats as the button is pressed.
+    pub cycle: Cycle,
+    /// The pulses it sends to modules outside it when the button is pressed after `i` presses,
+    /// for every `i` before its cycle first repeats.
+    pub sent: Vec<Vec<Signal<'a>>>,
+}
 
-    for i in 1..=1000 {
-        let (ls, hs) = simulate(
-            &input,
-            &mut conjunction_inputs,
-            &mut flip_flop_state,
-            i,
-            &mut ignored,
+impl Subcircuit<'_> {
+    /// Whether `condition` holds for the pulses sent out on each press.
+    fn holds(&self, condition: impl Fn(&[Signal]) -> bool) -> Periodic {
+        Periodic {
+            cycle: self.cycle,
+            holds: self.sent.iter().map(|sent| condition(sent)).collect(),
+        }
+    }
+}
+
+/// The modules reachable from more than one of the broadcaster's outputs.
+fn shared_modules<'a>(
+    modules: &'a HashMap<String, Module>,
+    broadcaster: &'a Module,
+) -> HashSet<&'a str> {
+    let mut reached_from = HashMap::new();
+    for entry in broadcaster.outputs.iter() {
+        let reached = search::reachable([entry.as_str()], |&name| match modules.get(name) {
+            Some(module) => module.outputs.iter().map(String::as_str).collect(),
+            None => vec![],
+        });
+        for name in reached.into_keys() {
+            *reached_from.entry(name).or_insert(0) += 1;
+        }
+    }
+
+    reached_from
+        .into_iter()
+        .filter(|&(_, entries)| entries > 1)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Splits the modules into a subcircuit per output of the broadcaster, each holding the modules
+/// only that output leads to, and finds how each one's state cycles.
+///
+/// Fails unless the subcircuits are independent counters: none gets pulses from outside it
+/// apart from the broadcaster, and each one's state repeats within [`MAX_PRESSES`] presses.
+pub fn subcircuits(modules: &HashMap<String, Module>) -> Result<Vec<Subcircuit<'_>>, SolveError> {
+    let Some((broadcaster_name, broadcaster)) = modules.get_key_value("broadcaster") else {
+        return Err(SolveError::UnsupportedInputShape(
+            "there is no broadcaster".to_string(),
+        ));
+    };
+    let shared = shared_modules(modules, broadcaster);
+
+    let mut subcircuits = vec![];
+    for entry in broadcaster.outputs.iter() {
+        if shared.contains(entry.as_str()) || !modules.contains_key(entry) {
+            return Err(SolveError::UnsupportedInputShape(format!(
+                "the broadcaster sends straight to `{entry}`, outside any subcircuit"
+            )));
+        }
+
+        let members = search::reachable([entry.as_str()], |&name| {
+            modules[name]
+                .outputs
+                .iter()
+                .map(String::as_str)
+                .filter(|&out| modules.contains_key(out) && !shared.contains(out))
+                .collect::<Vec<&str>>()
+        })
+        .into_keys()
+        .collect::<BTreeSet<&str>>();
+
+        for (name, module) in modules.iter() {
+            if name == broadcaster_name || members.contains(name.as_str()) {
+                continue;
+            }
+            if let Some(out) = module
+                .outputs
+                .iter()
+                .find(|out| members.contains(out.as_str()))
+            {
+                return Err(SolveError::UnsupportedInputShape(format!(
+                    "`{out}` in the subcircuit from `{entry}` gets pulses from `{name}` outside it"
+                )));
+            }
+        }
+
+        // Press the button on just this subcircuit until its state repeats.
+        let mut isolated = members
+            .iter()
+            .map(|&name| (name.to_string(), modules[name].clone()))
+            .collect::<HashMap<String, Module>>();
+        isolated.insert(
+            broadcaster_name.clone(),
+            Module {
+                outputs: vec![entry.clone()],
+                ..broadcaster.clone()
+            },
         );
-        lows += ls;
-        highs += hs;
+        let mut network = Network::new(&isolated);
+        let initial = network.state().clone();
+        let mut sent = vec![];
+        let found = cycle::find_cycle_hashed_within(initial, MAX_PRESSES, |state| {
+            network.state = state.clone();
+            let leaving = network
+                .press()
+                .into_iter()
+                .filter(|signal| !isolated.contains_key(signal.to))
+                .map(|signal| {
+                    // Borrow the names from `modules` rather than the isolated copy.
+                    let (from, module) = modules.get_key_value(signal.from).unwrap();
+                    let to = module.outputs.iter().find(|&out| out == signal.to).unwrap();
+                    Signal {
+                        from,
+                        to,
+                        pulse: signal.pulse,
+                    }
+                })
+                .collect();
+            sent.push(leaving);
+            network.state.clone()
+        });
+        let Some((cycle, _)) = found else {
+            return Err(SolveError::UnsupportedInputShape(format!(
+                "the subcircuit from `{entry}` doesn't repeat within {MAX_PRESSES} presses"
+            )));
+        };
+
+        subcircuits.push(Subcircuit {
+            entry,
+            modules: members,
+            cycle,
+            sent,
+        });
+    }
+
+    Ok(subcircuits)
+}
+
+/// Writes the modules as a Graphviz graph, with the broadcaster as a double circle, flip-flops
+/// as boxes and conjunctions as diamonds.
+///
+/// If the modules split into independent subcircuits, each is drawn as a cluster labelled with
+/// its cycle length. Otherwise the graph is labelled with why they don't.
+pub fn to_dot(modules: &HashMap<String, Module>) -> String {
+    let mut dot = String::from("digraph modules {\n");
+
+    let names = modules.keys().collect::<BTreeSet<&String>>();
+    for &name in names.iter() {
+        let shape = match modules[name].module_type {
+            ModuleType::Brodcaster => "doublecircle",
+            ModuleType::FlipFlop => "box",
+            ModuleType::Conjunction => "diamond",
+        };
+        dot.push_str(&format!("  \"{name}\" [shape={shape}];\n"));
+    }
+
+    match subcircuits(modules) {
+        Ok(subcircuits) => {
+            for (i, subcircuit) in subcircuits.iter().enumerate() {
+                dot.push_str(&format!(
+                    "  subgraph cluster_{i} {{\n    label=\"from {}, cycle of {} after {}\";\n",
+                    subcircuit.entry, subcircuit.cycle.period, subcircuit.cycle.offset
+                ));
+                for name in subcircuit.modules.iter() {
+                    dot.push_str(&format!("    \"{name}\";\n"));
+                }
+                dot.push_str("  }\n");
+            }
+        }
+        Err(err) => {
+            let reason = err.to_string().replace('"', "\\\"");
+            dot.push_str(&format!("  label=\"no subcircuits, {reason}\";\n"));
+        }
     }
 
-    // println!("LOWS {lows} HIGHS {highs}");
-    (lows * highs).to_string()
+    for &name in names.iter() {
+        for out in modules[name].outputs.iter() {
+            dot.push_str(&format!("  \"{name}\" -> \"{out}\";\n"));
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
 }
 
-fn get_rx_inputs(modules: &HashMap<String, Module>) -> HashMap<&str, usize> {
-    let direct_input = modules
+/// When `parent` sends `rx` a low pulse, as conditions on the presses of the subcircuits that
+/// decide it.
+///
+/// A conjunction joining subcircuits is taken to send its low pulse on a press where every input
+/// sends it a high one. That only holds if it doesn't remember a high pulse from an earlier press,
+/// so every input has to follow a high pulse with a low one before the press is over, which is
+/// checked. It also takes the high pulses to all arrive before any of the low ones that follow
+/// them, as they do when each counter resets at the same point in the press it's reached.
+fn rx_low_conditions(
+    modules: &HashMap<String, Module>,
+    subcircuits: &[Subcircuit],
+    parent: &str,
+) -> Result<Vec<Periodic>, SolveError> {
+    if let Some(subcircuit) = subcircuits
+        .iter()
+        .find(|subcircuit| subcircuit.modules.contains(parent))
+    {
+        return Ok(vec![subcircuit.holds(|sent| {
+            sent.iter().any(|signal| {
+                signal.from == parent && signal.to == "rx" && signal.pulse == Pulse::Low
+            })
+        })]);
+    }
+
+    // Otherwise it joins subcircuits together, which a conjunction does by sending a low pulse
+    // on the press its inputs all send it high ones.
+    if modules.get(parent).map(|module| &module.module_type) != Some(&ModuleType::Conjunction) {
+        return Err(SolveError::UnsupportedInputShape(format!(
+            "`{parent}` joins subcircuits in front of `rx` without being a conjunction"
+        )));
+    }
+    let inputs = modules
         .values()
-        .filter(|v| v.outputs.contains(&String::from("rx")))
-        .map(|v| v.name.as_str())
+        .filter(|module| module.outputs.iter().any(|out| out == parent))
+        .map(|module| module.name.as_str())
         .collect::<Vec<&str>>();
+    if let Some(input) = inputs.iter().find(|&&input| {
+        !subcircuits
+            .iter()
+            .any(|subcircuit| subcircuit.modules.contains(input))
+    }) {
+        return Err(SolveError::UnsupportedInputShape(format!(
+            "`{parent}` in front of `rx` gets pulses from `{input}`, which isn't in any subcircuit"
+        )));
+    }
 
-    if direct_input.len() != 1 {
-        panic!(
-            "Solution doesn't work for input, got {} direct inputs, expected 1",
-            direct_input.len()
-        );
+    let mut conditions = vec![];
+    for subcircuit in subcircuits {
+        let own_inputs = inputs
+            .iter()
+            .copied()
+            .filter(|&input| subcircuit.modules.contains(input))
+            .collect::<Vec<&str>>();
+        if own_inputs.is_empty() {
+            continue;
+        }
+
+        for &input in own_inputs.iter() {
+            let stays_high = subcircuit.sent.iter().any(|sent| {
+                sent.iter()
+                    .rfind(|signal| signal.from == input && signal.to == parent)
+                    .is_some_and(|signal| signal.pulse == Pulse::High)
+            });
+            if stays_high {
+                return Err(SolveError::UnsupportedInputShape(format!(
+                    "`{input}` can leave `{parent}` remembering a high pulse after a press"
+                )));
+            }
+        }
+
+        conditions.push(subcircuit.holds(|sent| {
+            own_inputs.iter().all(|&input| {
+                sent.iter().any(|signal| {
+                    signal.from == input && signal.to == parent && signal.pulse == Pulse::High
+                })
+            })
+        }));
     }
 
-    let direct_input = direct_input[0];
-    modules
-        .values()
-        .filter(|v| v.outputs.contains(&direct_input.to_string()))
-        .map(|v| (v.name.as_str(), 0))
-        .collect()
+    Ok(conditions)
 }
 
-pub fn solve_part_two<'a>(input: HashMap<String, Module>) -> String {
-    let mut conjunction_inputs = find_conjunction_inputs(&input);
-    let mut flip_flop_state = find_flip_flop_states(&input);
-
-    let mut rx_inputs = get_rx_inputs(&input);
+/// Splits the network into independent counters, finds the presses on which each would let a
+/// module in front of `rx` send it a low pulse, and combines them to find the first press on
+/// which they all do.
+pub fn solve_part_two(input: HashMap<String, Module>) -> Result<Answer, SolveError> {
+    let parents = input
+        .values()
+        .filter(|module| module.outputs.iter().any(|out| out == "rx"))
+        .map(|module| module.name.as_str())
+        .collect::<Vec<&str>>();
+    if parents.is_empty() {
+        return Err(SolveError::UnsupportedInputShape(
+            "nothing sends to `rx`".to_string(),
+        ));
+    }
 
-    for i in 1..=usize::MAX {
-        simulate(
-            &input,
-            &mut conjunction_inputs,
-            &mut flip_flop_state,
-            i,
-            &mut rx_inputs,
-        );
+    let subcircuits = subcircuits(&input)?;
 
-        if rx_inputs.values().all(|n| n > &0) {
-            break;
+    let mut earliest = None;
+    for parent in parents {
+        let conditions = rx_low_conditions(&input, &subcircuits, parent)?;
+        // The conditions hold for the press after the given number of presses.
+        if let Some(presses) = cycle::first_common_step(&conditions) {
+            earliest = Some(earliest.map_or(presses + 1, |e: usize| e.min(presses + 1)));
         }
     }
 
-    let ans = rx_inputs.values().fold(1, |acc, v| acc * v);
-    ans.to_string()
+    earliest
+        .map(Answer::from)
+        .ok_or_else(|| SolveError::Unsolvable("`rx` never gets a low pulse".to_string()))
 }
@@ -0,0 +1,45 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed<'a> = common::Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, SolveError};
+
+    use super::{solution, Day21};
+
+    // The example counts the plots for 6 steps rather than the 64 of part one.
+    #[test]
+    fn reachable_plots_example() {
+        let input = solution::parse(include_str!("../test_input.txt")).unwrap();
+        assert_eq!(solution::reachable_plots(&input, 6), Ok(16));
+    }
+
+    // 26501365 steps from the middle of the 11 wide example stop partway across a map.
+    #[test]
+    fn part_two_needs_whole_maps() {
+        let input = include_str!("../test_input.txt");
+        assert!(matches!(
+            solve_example::<Day21>(2, input),
+            Err(SolveError::Unsolvable(_))
+        ));
+    }
+}
@@ -1,107 +1,88 @@
-use std::collections::HashSet;
+use std::{collections::HashMap, hash::Hash};
 
-pub fn parse<'a>(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|l| l.chars().collect()).collect()
+use common::{search, Answer, Grid, ParseError, Point, SolveError, Source};
+
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Source::new(input).char_grid(input, ".#S")
 }
 
-pub fn solve_part_one<'a>(input: Vec<Vec<char>>) -> String {
-    let (start_x, start_y) = input
-        .iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, c)| c == &&'S')
-                .map(move |(x, _)| (x, y))
-        })
-        .collect::<Vec<(usize, usize)>>()
-        .first()
-        .unwrap()
-        .clone();
-
-    let width = input[0].len();
-    let height = input.len();
-
-    let mut last_turn: Vec<(usize, usize)> = vec![(start_x, start_y)];
-
-    for step in 0..=64 {
-        let mut next_turn = HashSet::new();
-
-        while let Some((x, y)) = last_turn.pop() {
-            let mut neighs = vec![];
-            if x > 0 {
-                neighs.push((x - 1, y));
-            }
-            if x < width - 1 {
-                neighs.push((x + 1, y));
-            }
-            if y > 0 {
-                neighs.push((x, y - 1));
-            }
-            if y < height - 1 {
-                neighs.push((x, y + 1));
-            }
-
-            neighs
-                .into_iter()
-                .filter(|(x, y)| input[*y][*x] != '#')
-                .for_each(|(x, y)| {
-                    next_turn.insert((x, y));
-                });
-        }
-
-        last_turn = next_turn.into_iter().collect();
-    }
+fn find_start(map: &Grid<char>) -> Result<Point, SolveError> {
+    map.position(|&c| c == 'S')
+        .ok_or_else(|| SolveError::UnsupportedInputShape("no start tile `S`".to_string()))
+}
 
-    last_turn.len().to_string()
+/// Counts the plots that can be reached in exactly `n` steps, given the fewest steps to each.
+///
+/// Stepping away and back again wastes two steps, so those are the plots at most `n` steps away
+/// that take an even number of steps fewer.
+fn count_reachable_in(steps: &HashMap<impl Eq + Hash, usize>, n: usize) -> usize {
+    steps
+        .values()
+        .filter(|&&s| s <= n && s % 2 == n % 2)
+        .count()
 }
 
-pub fn solve_part_two<'a>(input: Vec<Vec<char>>) -> String {
-    let (start_x, start_y) = input
-        .iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, c)| c == &&'S')
-                .map(move |(x, _)| (x as i128, y as i128))
-        })
-        .collect::<Vec<(i128, i128)>>()
-        .first()
-        .unwrap()
-        .clone();
-
-    let width = input[0].len() as i128;
-    let height = input.len() as i128;
-
-    let mut half_map_vals = vec![];
-    let half_map = ((width - 1) / 2) as usize;
+/// Counts the garden plots that can be reached in exactly `steps` steps from the start.
+pub(crate) fn reachable_plots(input: &Grid<char>, steps: usize) -> Result<usize, SolveError> {
+    let fewest_steps = search::reachable([find_start(input)?], |&p| {
+        input.neighbours4(p).filter(|&next| input[next] != '#')
+    });
+
+    Ok(count_reachable_in(&fewest_steps, steps))
+}
+
+pub fn solve_part_one(input: Grid<char>) -> Result<Answer, SolveError> {
+    Ok(reachable_plots(&input, 64)?.into())
+}
 
-    let mut last_turn: Vec<(i128, i128)> = vec![(start_x, start_y)];
-
-    for step in 1..=((width * 2) as usize + half_map) {
-        let mut next_turn = HashSet::new();
-
-        while let Some((x, y)) = last_turn.pop() {
-            [(x + 1, y), (x - 1, y), (x, y - 1), (x, y + 1)]
-                .into_iter()
-                .filter(|(x, y)| {
-                    input[(((y % height) + height) % height) as usize]
-                        [(((x % width) + height) % height) as usize]
-                        != '#'
-                })
-                .for_each(|(x, y)| {
-                    next_turn.insert((x, y));
-                });
-        }
-
-        last_turn = next_turn.into_iter().collect();
-        if step >= half_map && (step - half_map) % width as usize == 0 {
-            half_map_vals.push(last_turn.len());
-        }
+/// The steps the elf takes in part two.
+const STEPS: i128 = 26501365;
+
+pub fn solve_part_two(input: Grid<char>) -> Result<Answer, SolveError> {
+    let Point {
+        x: start_x,
+        y: start_y,
+    } = find_start(&input)?;
+
+    let width = input.width() as i128;
+    let height = input.height() as i128;
+
+    // The reachable count only grows quadratically per map crossed when starting in the middle.
+    if width != height || start_x as i128 * 2 + 1 != width || start_y as i128 * 2 + 1 != height {
+        return Err(SolveError::UnsupportedInputShape(
+            "the map must be square with the start in its center".to_string(),
+        ));
     }
+    let (start_x, start_y) = (start_x as i128, start_y as i128);
 
-    assert!(half_map_vals.len() == 3);
+    let half_map = ((width - 1) / 2) as usize;
+    // The quadratic only counts the plots after crossing a whole number of maps.
+    if (STEPS - half_map as i128) % width != 0 {
+        return Err(SolveError::Unsolvable(format!(
+            "{STEPS} steps don't reach the edge of a whole number of {width} wide maps"
+        )));
+    }
+    let max_steps = (width * 2) as usize + half_map;
+
+    // The map repeats infinitely in every direction, so only the plots within reach are explored.
+    let map = &input;
+    let fewest_steps = search::reachable([(start_x, start_y)], |&(x, y)| {
+        [(x + 1, y), (x - 1, y), (x, y - 1), (x, y + 1)]
+            .into_iter()
+            .filter(move |&(next_x, next_y)| {
+                let p = Point::new(
+                    next_x.rem_euclid(width) as usize,
+                    next_y.rem_euclid(height) as usize,
+                );
+                map[p] != '#'
+                    && (next_x - start_x).unsigned_abs() + (next_y - start_y).unsigned_abs()
+                        <= max_steps as u128
+            })
+    });
+
+    let half_map_vals = (0..3)
+        .map(|maps| count_reachable_in(&fewest_steps, half_map + maps * width as usize))
+        .collect::<Vec<usize>>();
 
     let hm = half_map as i128;
     let hm_vals = half_map_vals
@@ -109,18 +90,18 @@ pub fn solve_part_two<'a>(input: Vec<Vec<char>>) -> String {
         .map(|v| v as i128)
         .collect::<Vec<i128>>();
 
-    find_polynomial_value_at(
+    Ok(find_polynomial_value_at(
         [
             (hm, hm_vals[0]),
             (hm + width, hm_vals[1]),
             (hm + width * 2, hm_vals[2]),
         ],
-        26501365,
-    )
-    .to_string()
+        STEPS,
+    )?
+    .into())
 }
 
-fn find_polynomial_value_at(points: [(i128, i128); 3], x: i128) -> i128 {
+fn find_polynomial_value_at(points: [(i128, i128); 3], x: i128) -> Result<i128, SolveError> {
     let (a, b, c, denominator) = find_coefficients(points);
 
     let x_sq = x.pow(2);
@@ -129,22 +110,25 @@ fn find_polynomial_value_at(points: [(i128, i128); 3], x: i128) -> i128 {
     let bx = b * x;
 
     let ans_numerator = ax_sq + bx + c;
-    ans_numerator / denominator
+    if ans_numerator % denominator != 0 {
+        return Err(SolveError::Unsolvable(format!(
+            "the quadratic through {points:?} isn't a whole number at {x}"
+        )));
+    }
+    Ok(ans_numerator / denominator)
 }
 
+/// The coefficients of the quadratic through `points`, which must have distinct x values, all
+/// multiplied by the returned denominator.
 fn find_coefficients(points: [(i128, i128); 3]) -> (i128, i128, i128, i128) {
     let [(x1, y1), (x2, y2), (x3, y3)] = points;
 
     // Using Cramer's rule to solve the system of linear equations
     let denominator = -(x1 - x2) * (x1 - x3) * (x2 - x3);
 
-    if denominator == 0 {
-        panic!("Failed to find coefficients!");
-    }
-
-    let a = (y1 * (x2 - x3) + y2 * (x3 - x1) + y3 * (x1 - x2)) * -1;
+    let a = -(y1 * (x2 - x3) + y2 * (x3 - x1) + y3 * (x1 - x2));
     let b = y1 * (x2 + x3) * (x2 - x3) + y2 * (x3 + x1) * (x3 - x1) + y3 * (x1 + x2) * (x1 - x2);
-    let c = (y1 * x2 * x3 * (x2 - x3) + y2 * x3 * x1 * (x3 - x1) + y3 * x1 * x2 * (x1 - x2)) * -1;
+    let c = -(y1 * x2 * x3 * (x2 - x3) + y2 * x3 * x1 * (x3 - x1) + y3 * x1 * x2 * (x1 - x2));
 
     (a, b, c, denominator)
 }
@@ -0,0 +1,62 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed<'a> = solution::Inp;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError, Solution, SolveError};
+
+    use super::Day22;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day22>(1, input), Ok(Answer::Integer(5)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day22>(2, input), Ok(Answer::Integer(7)));
+    }
+
+    #[test]
+    fn rejects_misplaced_bricks() {
+        assert_eq!(
+            Day22::parse("1,0,1~1,2,1\n0,0,0~2,0,0").map(|_| ()),
+            Err(ParseError::new(2, 1, "a brick above the ground"))
+        );
+        assert_eq!(
+            Day22::parse("1,2,1~1,0,1").map(|_| ()),
+            Err(ParseError::new(1, 7, "an end no lower than the start"))
+        );
+    }
+
+    #[test]
+    fn bricks_must_not_overlap() {
+        assert_eq!(
+            solve_example::<Day22>(1, "1,0,1~1,2,1\n0,1,1~2,1,1"),
+            Err(SolveError::UnsupportedInputShape(
+                "brick 2 overlaps brick 1".to_string()
+            ))
+        );
+    }
+}
@@ -1,13 +1,27 @@
 use std::collections::{HashMap, HashSet};
 
+use common::{Answer, ParseError, SolveError, Source};
+
+// The (x, y, z) coordinates of the start and end of a brick.
+type Brick = ((usize, usize, usize), (usize, usize, usize));
+
 pub struct Inp {
-    bricks: HashMap<usize, ((usize, usize, usize), (usize, usize, usize))>,
+    bricks: HashMap<usize, Brick>,
     width: usize,  // x
     depth: usize,  // y
     height: usize, // z
 }
 
-pub fn parse<'a>(input: &'a str) -> Inp {
+fn parse_coords(src: &Source, s: &str) -> Result<(usize, usize, usize), ParseError> {
+    let (x, yz) = src.split_once(s, ",")?;
+    let (y, z) = src.split_once(yz, ",")?;
+
+    Ok((src.number(x)?, src.number(y)?, src.number(z)?))
+}
+
+pub fn parse(input: &str) -> Result<Inp, ParseError> {
+    let src = Source::new(input);
+
     let mut width = 0;
     let mut depth = 0;
     let mut height = 0;
@@ -16,88 +30,35 @@ pub fn parse<'a>(input: &'a str) -> Inp {
         .lines()
         .enumerate()
         .map(|(i, l)| {
-            let (start, end) = l.split_once("~").unwrap();
-            let s = start
-                .split(",")
-                .map(|n| n.parse().unwrap())
-                .collect::<Vec<usize>>();
-            let e = end
-                .split(",")
-                .map(|n| n.parse().unwrap())
-                .collect::<Vec<usize>>();
-
-            if s[0] > width {
-                width = s[0];
-            }
-            if e[0] > width {
-                width = e[0];
-            }
-            if s[1] > depth {
-                depth = s[1];
-            }
-            if e[1] > depth {
-                depth = e[1];
-            }
-            if s[2] > height {
-                height = s[2];
+            let (start, end) = src.split_once(l, "~")?;
+            let s = parse_coords(&src, start)?;
+            let e = parse_coords(&src, end)?;
+
+            // The ground is at z = 0, and every brick is given from its lowest corner.
+            if s.2 == 0 {
+                return Err(src.error(start, "a brick above the ground"));
             }
-            if e[2] > height {
-                height = e[2];
+            if s.0 > e.0 || s.1 > e.1 || s.2 > e.2 {
+                return Err(src.error(end, "an end no lower than the start"));
             }
 
-            (i + 1, ((s[0], s[1], s[2]), (e[0], e[1], e[2])))
+            width = width.max(s.0).max(e.0);
+            depth = depth.max(s.1).max(e.1);
+            height = height.max(s.2).max(e.2);
+
+            Ok((i + 1, (s, e)))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    Inp {
+    Ok(Inp {
         bricks,
         width,
         depth,
         height,
-    }
+    })
 }
 
-fn print_map_from_x(map: &Vec<Vec<Vec<usize>>>) {
-    for (z, level) in map.iter().enumerate().rev() {
-        for row in level.iter() {
-            let mut c = '.';
-            for id in row.iter() {
-                if id != &0 {
-                    c = format!("{id}")
-                        .chars()
-                        .collect::<Vec<char>>()
-                        .first()
-                        .unwrap()
-                        .clone();
-                }
-            }
-            print!("{}", c);
-        }
-        println!(" - {z}");
-    }
-}
-
-fn print_map_from_y(map: &Vec<Vec<Vec<usize>>>) {
-    for (z, level) in map.iter().enumerate().rev() {
-        for row in level.iter() {
-            let mut c = '.';
-            for id in row.iter() {
-                if id != &0 {
-                    c = format!("{id}")
-                        .chars()
-                        .collect::<Vec<char>>()
-                        .first()
-                        .unwrap()
-                        .clone();
-                }
-            }
-            print!("{}", c);
-        }
-        println!(" - {z}");
-    }
-}
-
-fn find_brick_deps(input: &Inp) -> HashMap<usize, HashSet<usize>> {
+fn find_brick_deps(input: &Inp) -> Result<HashMap<usize, HashSet<usize>>, SolveError> {
     // Indexed [z][y][x]
     let mut finished_map = vec![vec![vec![0; input.width + 1]; input.depth + 1]; input.height + 1];
 
@@ -106,7 +67,7 @@ fn find_brick_deps(input: &Inp) -> HashMap<usize, HashSet<usize>> {
         .iter()
         .map(|(id, ((_, _, s_z), _))| (id, s_z))
         .collect::<Vec<(&usize, &usize)>>();
-    bricks_sorting.sort_by(|(_, a), (_, b)| a.cmp(b));
+    bricks_sorting.sort_by_key(|&(id, z)| (z, id));
 
     let mut brick_z_offset = HashMap::new();
     for (id, low_z) in bricks_sorting.iter() {
@@ -130,8 +91,12 @@ fn find_brick_deps(input: &Inp) -> HashMap<usize, HashSet<usize>> {
             for y in brick.0 .1..=brick.1 .1 {
                 for z in brick.0 .2..=brick.1 .2 {
                     let act_z = z - lower_by;
+                    // Only bricks that overlapped to begin with can end up in the same place.
                     if finished_map[act_z][y][x] != 0 {
-                        panic!("THERE IS ALREADY SOMETHING AT {x} {y} {act_z} for brick {id}");
+                        return Err(SolveError::UnsupportedInputShape(format!(
+                            "brick {id} overlaps brick {}",
+                            finished_map[act_z][y][x]
+                        )));
                     }
 
                     finished_map[act_z][y][x] = **id;
@@ -160,14 +125,14 @@ fn find_brick_deps(input: &Inp) -> HashMap<usize, HashSet<usize>> {
         brick_deps.insert(**id, deps);
     }
 
-    brick_deps
+    Ok(brick_deps)
 }
 
 fn find_brick_inv_deps(
     brick_deps: &HashMap<usize, HashSet<usize>>,
 ) -> HashMap<usize, HashSet<usize>> {
     let mut brick_inv_deps = HashMap::new();
-    for (id, _) in brick_deps.iter() {
+    for id in brick_deps.keys() {
         let mut inv_deps = HashSet::new();
         for (id2, deps) in brick_deps.iter() {
             if id != id2 && deps.contains(id) {
@@ -179,11 +144,11 @@ fn find_brick_inv_deps(
     brick_inv_deps
 }
 
-pub fn solve_part_one<'a>(input: Inp) -> String {
-    let brick_deps = find_brick_deps(&input);
+pub fn solve_part_one(input: Inp) -> Result<Answer, SolveError> {
+    let brick_deps = find_brick_deps(&input)?;
     let brick_inv_deps = find_brick_inv_deps(&brick_deps);
 
-    brick_inv_deps
+    Ok(brick_inv_deps
         .values()
         .filter(|deps| {
             !deps
@@ -191,11 +156,11 @@ pub fn solve_part_one<'a>(input: Inp) -> String {
                 .any(|dep| brick_deps.get(dep).unwrap().len() == 1)
         })
         .count()
-        .to_string()
+        .into())
 }
 
-pub fn solve_part_two<'a>(input: Inp) -> String {
-    let brick_deps = find_brick_deps(&input);
+pub fn solve_part_two(input: Inp) -> Result<Answer, SolveError> {
+    let brick_deps = find_brick_deps(&input)?;
     let brick_inv_deps = find_brick_inv_deps(&brick_deps);
 
     let mut sum = 0;
@@ -230,5 +195,5 @@ pub fn solve_part_two<'a>(input: Inp) -> String {
         sum += disintegrated.len();
     }
 
-    sum.to_string()
+    Ok(sum.into())
 }
@@ -0,0 +1,62 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed<'a> = solution::Trails;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, ParseError, Solution, SolveError};
+
+    use super::Day23;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day23>(1, input), Ok(Answer::Integer(94)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day23>(2, input), Ok(Answer::Integer(154)));
+    }
+
+    #[test]
+    fn rejects_missing_ends() {
+        assert_eq!(
+            Day23::parse("#\n.").map(|_| ()),
+            Err(ParseError::new(1, 1, "a `.` to start from in the top row"))
+        );
+        assert_eq!(
+            Day23::parse("#.#\n#.#\n###").map(|_| ()),
+            Err(ParseError::new(3, 1, "a `.` to end at in the bottom row"))
+        );
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let input = "#.#\n###\n#.#";
+        let unsolvable = Err(SolveError::Unsolvable(
+            "no trail leads to the bottom row".into(),
+        ));
+        assert_eq!(solve_example::<Day23>(1, input), unsolvable);
+        assert_eq!(solve_example::<Day23>(2, input), unsolvable);
+    }
+}
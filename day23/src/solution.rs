@@ -1,53 +1,59 @@
 use std::collections::HashSet;
 
-pub fn parse<'a>(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|l| l.chars().collect()).collect()
+use common::{Answer, Direction, Grid, ParseError, Point, SolveError, Source};
+
+/// The trail map, with the goal in the bottom row marked as 'G'.
+#[derive(Debug, Clone)]
+pub struct Trails {
+    map: Grid<char>,
+    start: Point,
+}
+
+pub fn parse(input: &str) -> Result<Trails, ParseError> {
+    let src = Source::new(input);
+    let mut map = src.char_grid(input, ".#><^v")?;
+
+    // Every tile is ASCII, so byte offsets into a row are its columns.
+    let first_row = input.lines().next().unwrap_or(input);
+    let start_x = first_row
+        .find('.')
+        .ok_or_else(|| src.error(first_row, "a `.` to start from in the top row"))?;
+    let last_row = input.lines().last().unwrap_or(input);
+    let end_x = last_row
+        .find('.')
+        .ok_or_else(|| src.error(last_row, "a `.` to end at in the bottom row"))?;
+
+    let height = map.height();
+    map[Point::new(end_x, height - 1)] = 'G';
+
+    Ok(Trails {
+        map,
+        start: Point::new(start_x, 0),
+    })
 }
 
-fn find_longest(
-    map: &Vec<Vec<char>>,
-    path: &mut HashSet<(usize, usize)>,
-    x: usize,
-    y: usize,
-    width: usize,
-    height: usize,
-) -> Option<usize> {
-    let mut neighbours = vec![];
-    match map[y][x] {
+fn find_longest(map: &Grid<char>, path: &mut HashSet<Point>, p: Point) -> Option<usize> {
+    let neighbours = match map[p] {
         'G' => return Some(path.len() - 1),
-        '>' => neighbours.push((x + 1, y)),
-        '^' => neighbours.push((x, y - 1)),
-        '<' => neighbours.push((x - 1, y)),
-        'v' => neighbours.push((x, y + 1)),
-        '.' => {
-            if x > 0 {
-                neighbours.push((x - 1, y));
-            }
-            if y > 0 {
-                neighbours.push((x, y - 1));
-            }
-            if x < width - 1 {
-                neighbours.push((x + 1, y));
-            }
-            if y < height - 1 {
-                neighbours.push((x, y + 1));
-            }
+        slope @ ('>' | '^' | '<' | 'v') => {
+            let dir = Direction::from_arrow(slope).unwrap();
+            map.step(p, dir).into_iter().collect()
         }
         '#' => return None,
-        c => panic!("On invalid char {c}"),
-    }
+        // Parsing let nothing but `.` through besides the tiles above.
+        _ => map.neighbours4(p).collect::<Vec<Point>>(),
+    };
 
     let mut length = None;
-    for neigh in neighbours.iter() {
-        if path.contains(neigh) {
+    for neigh in neighbours {
+        if path.contains(&neigh) {
             // Already checked it
             continue;
         }
 
-        path.insert(*neigh);
-        let (neigh_x, neigh_y) = neigh;
-        let neigh_len = find_longest(map, path, *neigh_x, *neigh_y, width, height);
-        path.remove(neigh);
+        path.insert(neigh);
+        let neigh_len = find_longest(map, path, neigh);
+        path.remove(&neigh);
         if let Some(l) = neigh_len {
             let is_better = if let Some(l2) = length { l > l2 } else { true };
 
@@ -59,106 +65,55 @@ fn find_longest(
     length
 }
 
-pub fn solve_part_one<'a>(mut input: Vec<Vec<char>>) -> String {
-    let start_pos = input[0]
-        .iter()
-        .enumerate()
-        .filter(|(_, c)| c == &&'.')
-        .map(|(x, _)| (x, 0))
-        .collect::<Vec<(usize, usize)>>()[0];
-
-    let width = input[0].len();
-    let height = input.len();
-
-    let (end_x, end_y) = input[height - 1]
-        .iter()
-        .enumerate()
-        .filter(|(_, c)| c == &&'.')
-        .map(|(x, _)| (x, height - 1))
-        .collect::<Vec<(usize, usize)>>()[0];
-
-    // Mark the goal as 'G'
-    input[end_y][end_x] = 'G';
-
+pub fn solve_part_one(input: Trails) -> Result<Answer, SolveError> {
     let mut path = HashSet::new();
-    path.insert(start_pos);
+    path.insert(input.start);
 
-    find_longest(&input, &mut path, start_pos.0, start_pos.1, width, height)
-        .unwrap()
-        .to_string()
+    find_longest(&input.map, &mut path, input.start)
+        .map(Answer::from)
+        .ok_or_else(|| SolveError::Unsolvable("no trail leads to the bottom row".into()))
 }
 
 fn find_longest_p2(
-    map: &Vec<Vec<char>>,
-    path: &mut Vec<Vec<bool>>,
-    longest: &mut usize,
+    map: &Grid<char>,
+    path: &mut Grid<bool>,
+    longest: &mut Option<usize>,
     length: usize,
-    x: usize,
-    y: usize,
+    p: Point,
 ) {
-    let neighbours = match map[y][x] {
+    match map[p] {
         'G' => {
-            if &length > longest {
-                *longest = length;
+            if longest.is_none_or(|longest| length > longest) {
+                *longest = Some(length);
             }
             return;
         }
         '#' => return,
-        _ => [(-1, 0), (1, 0), (0, -1), (0, 1)].as_slice(),
+        _ => {}
     };
 
-    for (delta_x, delta_y) in neighbours {
-        let neigh_x = ((x as isize) + delta_x) as usize;
-        let neigh_y = ((y as isize) + delta_y) as usize;
-        let Some(neigh_tile) = map.get(neigh_y).and_then(|r| r.get(neigh_x)) else {
-            continue;
-        };
-
-        if path[neigh_y][neigh_x] || neigh_tile == &'#' {
+    for neigh in map.neighbours4(p) {
+        if path[neigh] || map[neigh] == '#' {
             // Already checked it
             continue;
         }
 
-        path[neigh_y][neigh_x] = true;
-        find_longest_p2(map, path, longest, length + 1, neigh_x, neigh_y);
-        path[neigh_y][neigh_x] = false;
+        path[neigh] = true;
+        find_longest_p2(map, path, longest, length + 1, neigh);
+        path[neigh] = false;
     }
 }
 
-pub fn solve_part_two<'a>(mut input: Vec<Vec<char>>) -> String {
-    let start_pos = input[0]
-        .iter()
-        .enumerate()
-        .filter(|(_, c)| c == &&'.')
-        .map(|(x, _)| (x, 0))
-        .collect::<Vec<(usize, usize)>>()[0];
-
-    let width = input[0].len();
-    let height = input.len();
-
-    let (end_x, end_y) = input[height - 1]
-        .iter()
-        .enumerate()
-        .filter(|(_, c)| c == &&'.')
-        .map(|(x, _)| (x, height - 1))
-        .collect::<Vec<(usize, usize)>>()[0];
-
-    // Mark the goal as 'G'
-    input[end_y][end_x] = 'G';
-
-    let mut visited = vec![vec![false; width]; height];
-    visited[start_pos.1][start_pos.0] = true;
-
-    let mut longest = 0;
-
-    find_longest_p2(
-        &input,
-        &mut visited,
-        &mut longest,
-        0,
-        start_pos.0,
-        start_pos.1,
-    );
-
-    longest.to_string()
+pub fn solve_part_two(input: Trails) -> Result<Answer, SolveError> {
+    let map = input.map;
+    let mut visited = Grid::filled(map.width(), map.height(), false);
+    visited[input.start] = true;
+
+    let mut longest = None;
+
+    find_longest_p2(&map, &mut visited, &mut longest, 0, input.start);
+
+    longest
+        .map(Answer::from)
+        .ok_or_else(|| SolveError::Unsolvable("no trail leads to the bottom row".into()))
 }
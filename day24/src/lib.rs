@@ -0,0 +1,61 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed<'a> = Vec<solution::HailStone>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(solution::solve_part_one(input))
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, SolveError};
+
+    use super::{solution, Day24};
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day24>(2, input), Ok(Answer::Integer(47)));
+    }
+
+    // Part one's example uses a test area from 7 to 27 rather than the puzzle's.
+    #[test]
+    fn intersections_example() {
+        let input = solution::parse(include_str!("../test_input.txt")).unwrap();
+        assert_eq!(solution::count_intersections(&input, 7.0..=27.0), 2);
+    }
+
+    #[test]
+    fn part_two_needs_a_rock_through_every_stone() {
+        let two = "19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1, -2";
+        assert_eq!(
+            solve_example::<Day24>(2, two),
+            Err(SolveError::UnsupportedInputShape(
+                "needs at least 3 hailstones, got 2".into()
+            ))
+        );
+
+        // Hailstones that stand still at the corners of a triangle can't be hit in a straight line.
+        let triangle = "0, 0, 0 @ 0, 0, 0\n1, 0, 0 @ 0, 0, 0\n0, 1, 0 @ 0, 0, 0";
+        assert_eq!(
+            solve_example::<Day24>(2, triangle),
+            Err(SolveError::Unsolvable(
+                "no rock hits all the hailstones".into()
+            ))
+        );
+    }
+}
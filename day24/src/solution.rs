@@ -1,5 +1,6 @@
-use std::ops::{Add, Mul};
+use std::ops::{Add, Mul, RangeInclusive};
 
+use common::{Answer, ParseError, SolveError, Source};
 use z3::{
     ast::{Ast, Int, Real},
     Config, SatResult,
@@ -15,25 +16,27 @@ pub struct HailStone {
     delta_z: i128,
 }
 
-pub fn parse<'a>(input: &str) -> Vec<HailStone> {
+pub fn parse(input: &str) -> Result<Vec<HailStone>, ParseError> {
+    let src = Source::new(input);
+
     input
         .lines()
         .map(|l| {
-            let (pos, velocity) = l.split_once(" @ ").unwrap();
-            let (pos_x, pos_yz) = pos.split_once(", ").unwrap();
-            let (pos_y, pos_z) = pos_yz.split_once(", ").unwrap();
-
-            let (delta_x, delta_yz) = velocity.split_once(", ").unwrap();
-            let (delta_y, delta_z) = delta_yz.split_once(", ").unwrap();
-
-            HailStone {
-                x: pos_x.trim().parse().unwrap(),
-                y: pos_y.trim().parse().unwrap(),
-                z: pos_z.trim().parse().unwrap(),
-                delta_x: delta_x.trim().parse().unwrap(),
-                delta_y: delta_y.trim().parse().unwrap(),
-                delta_z: delta_z.trim().parse().unwrap(),
-            }
+            let (pos, velocity) = src.split_once(l, " @ ")?;
+            let (pos_x, pos_yz) = src.split_once(pos, ", ")?;
+            let (pos_y, pos_z) = src.split_once(pos_yz, ", ")?;
+
+            let (delta_x, delta_yz) = src.split_once(velocity, ", ")?;
+            let (delta_y, delta_z) = src.split_once(delta_yz, ", ")?;
+
+            Ok(HailStone {
+                x: src.number(pos_x.trim())?,
+                y: src.number(pos_y.trim())?,
+                z: src.number(pos_z.trim())?,
+                delta_x: src.number(delta_x.trim())?,
+                delta_y: src.number(delta_y.trim())?,
+                delta_z: src.number(delta_z.trim())?,
+            })
         })
         .collect()
 }
@@ -64,15 +67,11 @@ impl Line {
     }
 }
 
-const RANGE_MIN: f64 = 200000000000000.0;
-const RANGE_MAX: f64 = 400000000000000.0;
-// const RANGE_MIN: f64 = 7.0;
-// const RANGE_MAX: f64 = 27.0;
-pub fn solve_part_one<'a>(input: Vec<HailStone>) -> String {
-    let lines: Vec<Line> = input
-        .iter()
-        .map(|stone| Line::from_hailstone(stone))
-        .collect();
+const TEST_AREA: RangeInclusive<f64> = 200000000000000.0..=400000000000000.0;
+
+/// Counts the pairs of hailstones whose future paths cross inside `area` on both the x and y axis.
+pub(crate) fn count_intersections(input: &[HailStone], area: RangeInclusive<f64>) -> usize {
+    let lines: Vec<Line> = input.iter().map(Line::from_hailstone).collect();
 
     let mut intersections = 0;
     for (i, line_1) in lines.iter().enumerate() {
@@ -86,7 +85,6 @@ pub fn solve_part_one<'a>(input: Vec<HailStone>) -> String {
 
             if delta == 0 {
                 // No intersections, they are parallel
-                println!("Line {i} and {j} are paralell");
                 continue;
             }
 
@@ -94,30 +92,17 @@ pub fn solve_part_one<'a>(input: Vec<HailStone>) -> String {
             let intersect_x = ((line_2.b * line_1.c - line_1.b * line_2.c) as f64) / delta;
             let intersect_y = ((line_1.a * line_2.c - line_2.a * line_1.c) as f64) / delta;
 
-            if intersect_x >= RANGE_MIN
-                && intersect_x <= RANGE_MAX
-                && intersect_y >= RANGE_MIN
-                && intersect_y <= RANGE_MAX
-            {
-                println!("Line {i} collides with {j} at ({intersect_x}, {intersect_y})");
-
-                println!(
-                    "\tHailstone \n\t{:?}\n\t{:?}\n\n\t{:?}\n\t{:?}",
-                    input[i], input[j], lines[i], lines[j]
-                );
-
+            if area.contains(&intersect_x) && area.contains(&intersect_y) {
                 // Check if it happened in the past or future.
                 if (input[i].delta_x < 0 && (input[i].x as f64) < intersect_x)
                     || (input[i].delta_x >= 0 && (input[i].x as f64) > intersect_x)
                 {
-                    println!("\tCollision happened in the past for line {i}");
                     continue;
                 }
 
                 if (input[j].delta_x < 0 && (input[j].x as f64) < intersect_x)
                     || (input[j].delta_x >= 0 && (input[j].x as f64) > intersect_x)
                 {
-                    println!("\tCollision happened in the past for line {j}");
                     continue;
                 }
                 intersections += 1;
@@ -125,10 +110,22 @@ pub fn solve_part_one<'a>(input: Vec<HailStone>) -> String {
         }
     }
 
-    intersections.to_string()
+    intersections
 }
 
-pub fn solve_part_two<'a>(input: Vec<HailStone>) -> String {
+pub fn solve_part_one(input: Vec<HailStone>) -> Answer {
+    count_intersections(&input, TEST_AREA).into()
+}
+
+pub fn solve_part_two(input: Vec<HailStone>) -> Result<Answer, SolveError> {
+    // Three hailstones are enough to pin down the rock's position and velocity.
+    let Some(stones) = input.get(0..3) else {
+        return Err(SolveError::UnsupportedInputShape(format!(
+            "needs at least 3 hailstones, got {}",
+            input.len()
+        )));
+    };
+
     let cfg = Config::new();
     let ctx = z3::Context::new(&cfg);
 
@@ -142,10 +139,7 @@ pub fn solve_part_two<'a>(input: Vec<HailStone>) -> String {
     let f_dy = Real::new_const(&ctx, "fdy");
     let f_dz = Real::new_const(&ctx, "fdz");
 
-    // We only need the first 3.
-    for i in 0..=2 {
-        let s = &input[i];
-
+    for (i, s) in stones.iter().enumerate() {
         let t = Real::new_const(&ctx, format!("t{i}"));
 
         solver.assert(&t.ge(&Real::from_int(&Int::from_i64(&ctx, 0))));
@@ -166,15 +160,19 @@ pub fn solve_part_two<'a>(input: Vec<HailStone>) -> String {
         solver.assert(&Ast::_eq(&z_eq, &z_eq_nums));
     }
 
+    let unsolvable = || SolveError::Unsolvable("no rock hits all the hailstones".into());
     if solver.check() != SatResult::Sat {
-        println!("FAILED TO FIND ANSWER! Got result {:?}", solver.check());
+        return Err(unsolvable());
     }
 
     let resp = solver
         .get_model()
-        .unwrap()
-        .eval(&(f_x + f_y + f_z), true)
-        .unwrap();
-
-    resp.to_string()
+        .and_then(|model| model.eval(&(f_x + f_y + f_z), true))
+        .ok_or_else(unsolvable)?;
+
+    Ok(match resp.as_real() {
+        Some((numerator, 1)) => numerator.into(),
+        Some((numerator, denominator)) => format!("{numerator}/{denominator}").into(),
+        None => resp.to_string().into(),
+    })
 }
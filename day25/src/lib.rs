@@ -0,0 +1,53 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub mod solution;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Parsed<'a> = Vec<(&'a str, &'a str)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{solution::solve_example, Answer, SolveError};
+
+    use super::Day25;
+
+    #[test]
+    fn part_one_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(solve_example::<Day25>(1, input), Ok(Answer::Integer(54)));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = include_str!("../test_input.txt");
+        assert_eq!(
+            solve_example::<Day25>(2, input),
+            Err(SolveError::NotImplemented)
+        );
+    }
+
+    #[test]
+    fn part_one_needs_three_wires_to_cut() {
+        assert_eq!(
+            solve_example::<Day25>(1, "a: b c\nb: c d\n"),
+            Err(SolveError::UnsupportedInputShape(
+                "fewer than three wires separate a from b".into()
+            ))
+        );
+    }
+}
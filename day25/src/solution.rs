@@ -1,16 +1,20 @@
 use std::collections::{HashMap, HashSet};
 
-pub fn parse<'a>(input: &'a str) -> Vec<(&str, &str)> {
-    input
-        .lines()
-        .flat_map(|l| {
-            let (a, bs) = l.split_once(": ").unwrap();
-            bs.split(" ").map(move |b| (a, b))
-        })
-        .collect()
+use common::{search, Answer, ParseError, SolveError, Source};
+
+pub fn parse(input: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    let src = Source::new(input);
+
+    let mut pairs = vec![];
+    for l in input.lines() {
+        let (a, bs) = src.split_once(l, ": ")?;
+        pairs.extend(bs.split(" ").map(|b| (a, b)));
+    }
+
+    Ok(pairs)
 }
 
-fn to_edges<'a>(pairs: &Vec<(&'a str, &'a str)>) -> HashMap<&'a str, HashSet<&'a str>> {
+fn to_edges<'a>(pairs: &[(&'a str, &'a str)]) -> HashMap<&'a str, HashSet<&'a str>> {
     let mut vertices = HashSet::new();
     for (a, b) in pairs.iter() {
         if !vertices.contains(a) {
@@ -34,83 +38,89 @@ fn to_edges<'a>(pairs: &Vec<(&'a str, &'a str)>) -> HashMap<&'a str, HashSet<&'a
     edges
 }
 
-fn find_frequencies<'a>(
-    edges: &HashMap<&'a str, HashSet<&'a str>>,
-) -> HashMap<(&'a str, &'a str), usize> {
-    let mut frequencies = HashMap::new();
-    for e in edges.keys() {
-        let mut to_check = vec![];
-        to_check.insert(0, *e);
-
-        let mut visited = HashSet::new();
-        visited.insert(*e);
-
-        while let Some(pos) = to_check.pop() {
-            for &next in &edges[pos] {
-                if visited.insert(next) {
-                    let key = if pos < next { (pos, next) } else { (next, pos) };
-
-                    let freq = frequencies.entry(key).or_insert(0);
-                    *freq += 1;
-
-                    to_check.insert(0, next);
-                }
-            }
-        }
-    }
-    frequencies
+/// How many times each wire is used in each direction by a set of paths, where using it back
+/// the other way cancels out.
+type Flow<'a> = HashMap<(&'a str, &'a str), i32>;
+
+/// The components next to `pos` that can be reached over a wire the paths in `flow` don't use in
+/// that direction yet.
+fn residual<'a: 'b, 'b>(
+    edges: &'b HashMap<&'a str, HashSet<&'a str>>,
+    flow: &'b Flow<'a>,
+    pos: &'a str,
+) -> impl Iterator<Item = &'a str> + 'b {
+    edges[pos]
+        .iter()
+        .copied()
+        .filter(move |&next| flow.get(&(pos, next)).is_none_or(|&used| used < 1))
 }
 
-fn find_partition_size(edges: &HashMap<&str, HashSet<&str>>, divided: &Vec<(&str, &str)>) -> usize {
-    let node = *edges.keys().next().unwrap();
-    let mut size = 1;
-
-    let mut to_check = vec![];
-    to_check.insert(0, node);
-
-    let mut visited = HashSet::new();
-    visited.insert(node);
-
-    while let Some(pos) = to_check.pop() {
-        for &next in &edges[pos] {
-            let key = if pos < next { (pos, next) } else { (next, pos) };
-
-            if divided.contains(&key) {
-                continue;
-            }
-
-            if visited.insert(next) {
-                size += 1;
-                to_check.insert(0, next);
-            }
+/// Finds up to `limit` paths from `source` to `sink` that share no wires, returning how many there
+/// are and how many components can still be reached from `source` once they're all used.
+///
+/// When there are fewer than `limit`, that's as many as there are at all, and the components still
+/// reachable are the side of `source` in a smallest set of wires separating it from `sink`.
+fn disjoint_paths<'a>(
+    edges: &HashMap<&'a str, HashSet<&'a str>>,
+    source: &'a str,
+    sink: &'a str,
+    limit: usize,
+) -> (usize, usize) {
+    let mut flow = Flow::new();
+    let mut paths = 0;
+
+    while paths < limit {
+        let Some(found) = search::bfs(
+            [source],
+            |&pos| residual(edges, &flow, pos),
+            |&pos| pos == sink,
+        ) else {
+            break;
+        };
+
+        for step in found.path.windows(2) {
+            *flow.entry((step[0], step[1])).or_default() += 1;
+            *flow.entry((step[1], step[0])).or_default() -= 1;
         }
+        paths += 1;
     }
 
-    size
+    let side = search::reachable([source], |&pos| residual(edges, &flow, pos));
+    (paths, side.len())
 }
 
-pub fn solve_part_one<'a>(input: Vec<(&str, &str)>) -> String {
+/// Splits the components in two by cutting three wires, and multiplies the sizes of the halves.
+///
+/// The first component (by name) and another are on different sides exactly when at most three
+/// paths between them share no wires. Each other component is tried in turn, which finds the cut
+/// without guessing which wires are in it.
+pub fn solve_part_one(input: Vec<(&str, &str)>) -> Result<Answer, SolveError> {
     let edges = to_edges(&input);
-    let frequencies = find_frequencies(&edges);
 
-    let mut sorted = frequencies
-        .iter()
-        .map(|(a, b)| (*a, *b))
-        .collect::<Vec<((&str, &str), usize)>>();
-    sorted.sort_by_key(|(_, freq)| freq.clone());
-    sorted.reverse();
-
-    let divided = sorted
-        .iter()
-        .take(3)
-        .map(|(e, _)| *e)
-        .collect::<Vec<(&str, &str)>>();
-
-    let size = find_partition_size(&edges, &divided);
+    let mut components = edges.keys().copied().collect::<Vec<&str>>();
+    components.sort();
+    let Some((&source, sinks)) = components.split_first() else {
+        return Err(SolveError::Unsolvable("there are no components".into()));
+    };
+
+    for &sink in sinks {
+        match disjoint_paths(&edges, source, sink, 4) {
+            (4, _) => continue,
+            (3, size) => return Ok((size * (components.len() - size)).into()),
+            _ => {
+                return Err(SolveError::UnsupportedInputShape(format!(
+                    "fewer than three wires separate {source} from {sink}"
+                )))
+            }
+        }
+    }
 
-    (size * (edges.len() - size)).to_string()
+    Err(SolveError::Unsolvable(
+        "no three wires split the components in two".into(),
+    ))
 }
 
-pub fn solve_part_two<'a>(input: Vec<(&str, &str)>) -> String {
-    panic!("Part two is not available!")
+/// The last day only has one puzzle, the second star is given for finishing all others.
+pub fn solve_part_two(_input: Vec<(&str, &str)>) -> Result<Answer, SolveError> {
+    Err(SolveError::NotImplemented)
 }
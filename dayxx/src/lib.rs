@@ -0,0 +1,21 @@
+use common::{Answer, ParseError, SolveError, Solution};
+
+pub mod solution;
+
+pub struct DayXX;
+
+impl Solution for DayXX {
+    type Parsed<'a> = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        solution::parse(input)
+    }
+
+    fn solve_part_one(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_one(input)
+    }
+
+    fn solve_part_two(input: Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        solution::solve_part_two(input)
+    }
+}
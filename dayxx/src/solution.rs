@@ -1,11 +1,13 @@
-pub fn parse<'a>(input: &str) -> impl Iterator<Item = u8> + 'a {
+use common::{Answer, ParseError, SolveError};
+
+pub fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
     todo!("Parse is not yet implemented");
 }
 
-pub fn solve_part_one<'a>(input: impl Iterator<Item = u8>) -> String {
-    todo!("Part one is not yet implemented");
+pub fn solve_part_one(input: Vec<u8>) -> Result<Answer, SolveError> {
+    Err(SolveError::NotImplemented)
 }
 
-pub fn solve_part_two<'a>(input: impl Iterator<Item = u8>) -> String {
-    todo!("Part two is not yet implemented");
+pub fn solve_part_two(input: Vec<u8>) -> Result<Answer, SolveError> {
+    Err(SolveError::NotImplemented)
 }